@@ -0,0 +1,43 @@
+//! Timing shared by the `harness = false` benchmarks.
+
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc_2024::input::normalize;
+
+/// The real input for `day` if it is present, otherwise the example.
+pub fn read_input(day: u8) -> String {
+    let inputs = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+    let name = format!("day{:02}.txt", day);
+    let input = fs::read_to_string(inputs.join(&name))
+        .or_else(|_| fs::read_to_string(inputs.join("examples").join(&name)))
+        .expect("no input or example");
+    normalize(&input).into_owned()
+}
+
+/// Runs `f` `iterations` times, prints the min, mean and max time under
+/// `label` and returns the last result.
+pub fn bench<T>(label: &str, iterations: u32, mut f: impl FnMut() -> T) -> T {
+    let mut times = Vec::new();
+    let mut result = None;
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        result = Some(f());
+        times.push(start.elapsed());
+    }
+    let min = times.iter().min().unwrap();
+    let max = times.iter().max().unwrap();
+    let mean = times.iter().sum::<Duration>() / times.len() as u32;
+    println!(
+        "{:<32}  min {:>10.2?}  mean {:>10.2?}  max {:>10.2?}  (n = {})",
+        label,
+        min,
+        mean,
+        max,
+        times.len()
+    );
+    result.unwrap()
+}
@@ -0,0 +1,41 @@
+//! Day 6 part 2: the loop detector against the brute-force search it
+//! replaced, which walks a copy of the map with each candidate obstacle
+//! added, one step at a time.
+//!
+//! `cargo bench --bench day06` uses `inputs/day06.txt`, or the example if
+//! the real input is missing.
+
+mod common;
+
+use std::collections::HashSet;
+
+use aoc_2024::solutions::{
+    day06::{find_guards, loop_obstacles, Day06, Map, Outcome, Patrol, Tile},
+    Solution,
+};
+use common::{bench, read_input};
+
+fn brute_force(map: &Map) -> usize {
+    let start = find_guards(map)[0];
+    let visited = Patrol::new(map, start)
+        .map(|(pos, _)| pos)
+        .collect::<HashSet<_>>();
+    visited
+        .into_iter()
+        .filter(|&p| p != start.0)
+        .filter(|&p| {
+            let mut map = map.clone();
+            map[&p] = Tile::Obstacle;
+            matches!(Patrol::new(&map, start).run(), Outcome::Loop(_))
+        })
+        .count()
+}
+
+fn main() {
+    let map = Day06::parse(&read_input(6)).unwrap();
+    let old = bench("day 6 part 2, brute force", 3, || brute_force(&map));
+    let new = bench("day 6 part 2, jump tables", 20, || {
+        loop_obstacles(&map).len()
+    });
+    assert_eq!(old, new);
+}
@@ -0,0 +1,89 @@
+//! Day 7 with `+`, `*` and `||`: the backward search against trying every
+//! combination of operators, on generated equations with more and more
+//! operands, half of them with a test value off by one, and on the puzzle
+//! input.
+//!
+//! `cargo bench --bench day07`. The exhaustive search is only timed up to 12
+//! operands, since each extra operand triples its work.
+
+mod common;
+
+use aoc_2024::solutions::day07::{calibrate, BuiltinOperator, Operator, PART2_OPERATORS};
+use common::{bench, read_input};
+
+// Deterministic pseudo-random numbers, so that every run times the same input.
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, n: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % n
+    }
+}
+
+fn equations(count: usize, operands: usize, rng: &mut Lcg) -> Vec<(u64, Vec<u64>)> {
+    let mut equations = Vec::new();
+    while equations.len() < count {
+        let numbers = (0..operands).map(|_| rng.below(9) + 1).collect::<Vec<_>>();
+        // Mostly additions, so that the result fits in a u64.
+        let total = numbers[1..].iter().try_fold(numbers[0], |acc, &n| {
+            let op = match rng.below(10) {
+                0 => BuiltinOperator::Concat(10),
+                1 | 2 => BuiltinOperator::Mul,
+                _ => BuiltinOperator::Add,
+            };
+            op.apply(acc, n)
+        });
+        if let Some(total) = total {
+            let total = total + (equations.len() % 2) as u64;
+            equations.push((total, numbers));
+        }
+    }
+    equations
+}
+
+fn exhaustive(expected: u64, acc: u64, rest: &[u64]) -> bool {
+    match rest.split_first() {
+        None => acc == expected,
+        Some((&n, rest)) => PART2_OPERATORS.iter().any(|op| {
+            op.apply(acc, n)
+                .is_some_and(|acc| exhaustive(expected, acc, rest))
+        }),
+    }
+}
+
+fn main() {
+    let input = read_input(7);
+    bench("puzzle input, backward", 20, || {
+        calibrate(&input, PART2_OPERATORS).unwrap()
+    });
+
+    let mut rng = Lcg(7);
+    for operands in [8, 12, 20, 25] {
+        let equations = equations(40, operands, &mut rng);
+        let input = equations
+            .iter()
+            .map(|(total, numbers)| {
+                let numbers = numbers.iter().map(u64::to_string).collect::<Vec<_>>();
+                format!("{}: {}\n", total, numbers.join(" "))
+            })
+            .collect::<String>();
+
+        let new = bench(&format!("{} operands, backward", operands), 20, || {
+            calibrate(&input, PART2_OPERATORS).unwrap()
+        });
+        if operands <= 12 {
+            let old = bench(&format!("{} operands, exhaustive", operands), 3, || {
+                equations
+                    .iter()
+                    .filter(|(total, numbers)| exhaustive(*total, numbers[0], &numbers[1..]))
+                    .map(|(total, _)| total)
+                    .sum::<u64>()
+            });
+            assert_eq!(old, new);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Writing solver data as JSON or CSV for diffing and plotting.
+
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format `{}` (expected `json` or `csv`)", s)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+        })
+    }
+}
+
+/// A single value in a `Table`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Field {
+    Int(i64),
+    Text(String),
+}
+
+impl From<isize> for Field {
+    fn from(value: isize) -> Self {
+        Field::Int(value as i64)
+    }
+}
+
+impl From<String> for Field {
+    fn from(value: String) -> Self {
+        Field::Text(value)
+    }
+}
+
+impl From<&str> for Field {
+    fn from(value: &str) -> Self {
+        Field::Text(value.to_string())
+    }
+}
+
+/// Records with the same named fields, written as a JSON array of objects or
+/// as CSV with a header line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<Field>>,
+}
+
+impl Table {
+    pub fn new(columns: &[&'static str]) -> Self {
+        Self {
+            columns: columns.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a record, with one field per column.
+    pub fn push(&mut self, row: Vec<Field>) {
+        assert_eq!(row.len(), self.columns.len(), "wrong number of fields");
+        self.rows.push(row);
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn write(&self, format: Format) -> String {
+        match format {
+            Format::Json => self.to_json(),
+            Format::Csv => self.to_csv(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let records = self
+            .rows
+            .iter()
+            .map(|row| {
+                let fields = self
+                    .columns
+                    .iter()
+                    .zip(row)
+                    .map(|(column, field)| {
+                        format!("{}: {}", json_string(column), json_value(field))
+                    })
+                    .collect::<Vec<_>>();
+                format!("  {{{}}}", fields.join(", "))
+            })
+            .collect::<Vec<_>>();
+        if records.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n{}\n]\n", records.join(",\n"))
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = self.columns.join(",") + "\n";
+        for row in &self.rows {
+            let fields = row.iter().map(csv_value).collect::<Vec<_>>();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_value(field: &Field) -> String {
+    match field {
+        Field::Int(n) => n.to_string(),
+        Field::Text(s) => json_string(s),
+    }
+}
+
+fn csv_value(field: &Field) -> String {
+    match field {
+        Field::Int(n) => n.to_string(),
+        Field::Text(s) if s.contains([',', '"', '\n']) => format!("\"{}\"", s.replace('"', "\"\"")),
+        Field::Text(s) => s.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        let mut table = Table::new(&["x", "name"]);
+        table.push(vec![Field::from(1), Field::from("plain")]);
+        table.push(vec![Field::from(-2), Field::from("a \"b\", c")]);
+        table
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            table().write(Format::Json),
+            "[\n  {\"x\": 1, \"name\": \"plain\"},\n  {\"x\": -2, \"name\": \"a \\\"b\\\", c\"}\n]\n"
+        );
+        assert_eq!(Table::new(&["x"]).write(Format::Json), "[]\n");
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            table().write(Format::Csv),
+            "x,name\n1,plain\n-2,\"a \"\"b\"\", c\"\n"
+        );
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+        assert_eq!(Format::Json.to_string(), "json");
+    }
+}
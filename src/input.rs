@@ -0,0 +1,200 @@
+use anyhow::{anyhow, Context, Result};
+use std::{
+    borrow::Cow,
+    env,
+    ffi::OsString,
+    fs, io,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable overriding the directory puzzle inputs are read from.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `dayNN.txt` in the inputs directory, or in its `examples` subdirectory.
+    Day {
+        day: u8,
+        example: bool,
+    },
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// Interprets a command-line path, where `-` stands for stdin.
+    pub fn from_arg(path: &Path) -> Self {
+        if path == Path::new("-") {
+            Source::Stdin
+        } else {
+            Source::File(path.to_path_buf())
+        }
+    }
+}
+
+/// Resolves and reads puzzle inputs relative to an inputs directory.
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    dir: PathBuf,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Picks the inputs directory from, in order: `dir`, the
+    /// `AOC_INPUTS_DIR` environment variable, `./inputs` if it exists, and
+    /// finally the `inputs` directory of the crate itself.
+    pub fn resolve(dir: Option<PathBuf>) -> Self {
+        Self::new(resolve_dir(dir, env::var_os(INPUTS_DIR_VAR), "inputs"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8, example: bool) -> PathBuf {
+        let dir = if example {
+            self.dir.join("examples")
+        } else {
+            self.dir.clone()
+        };
+        dir.join(format!("day{:02}.txt", day))
+    }
+
+    pub fn read(&self, source: &Source) -> Result<String> {
+        match source {
+            Source::Day { day, example } => read_file(&self.path(*day, *example)),
+            Source::File(path) => read_file(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("failed to read input from stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Picks `dir`, else the directory named by `var`, else `./name` if it
+/// exists, else `name` inside the crate directory.
+pub(crate) fn resolve_dir(dir: Option<PathBuf>, var: Option<OsString>, name: &str) -> PathBuf {
+    dir.or_else(|| var.filter(|v| !v.is_empty()).map(PathBuf::from))
+        .unwrap_or_else(|| {
+            let local = PathBuf::from(name);
+            if local.is_dir() {
+                local
+            } else {
+                Path::new(env!("CARGO_MANIFEST_DIR")).join(name)
+            }
+        })
+}
+
+/// Puts raw puzzle input into the form every parser expects: no byte order
+/// mark, `\n` line endings, no trailing whitespace on any line and no blank
+/// lines at the end. Every non-empty result ends with a single `\n`.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let lines = input.lines().map(str::trim_end).collect::<Vec<_>>();
+    let len = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |i| i + 1);
+
+    let mut normalized = String::with_capacity(input.len());
+    for line in &lines[..len] {
+        normalized.push_str(line);
+        normalized.push('\n');
+    }
+
+    if normalized == input {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    if !path.exists() {
+        return Err(anyhow!(
+            "input file {} does not exist (use --input, --inputs-dir or {} to point elsewhere)",
+            path.display(),
+            INPUTS_DIR_VAR
+        ));
+    }
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a\nb\n"), "a\nb\n");
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed(_)));
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("\u{feff}a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalize("a  \n\nb\t\n\n \n"), "a\n\nb\n");
+        assert_eq!(normalize(" a\n"), " a\n");
+        assert_eq!(normalize("\n\n"), "");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn test_path() {
+        let inputs = Inputs::new("data");
+        assert_eq!(inputs.path(7, false), Path::new("data/day07.txt"));
+        assert_eq!(inputs.path(12, true), Path::new("data/examples/day12.txt"));
+    }
+
+    #[test]
+    fn test_resolve_dir() {
+        assert_eq!(
+            resolve_dir(Some("a".into()), Some("b".into()), "inputs"),
+            Path::new("a")
+        );
+        assert_eq!(
+            resolve_dir(None, Some("b".into()), "inputs"),
+            Path::new("b")
+        );
+        assert!(resolve_dir(None, Some("".into()), "inputs").ends_with("inputs"));
+    }
+
+    #[test]
+    fn test_source_from_arg() {
+        assert_eq!(Source::from_arg(Path::new("-")), Source::Stdin);
+        assert_eq!(
+            Source::from_arg(Path::new("x.txt")),
+            Source::File("x.txt".into())
+        );
+    }
+
+    #[test]
+    fn test_missing_file_is_named() {
+        let inputs = Inputs::new("does-not-exist");
+        let error = inputs
+            .read(&Source::Day {
+                day: 3,
+                example: false,
+            })
+            .unwrap_err();
+        assert!(error.to_string().contains("does-not-exist/day03.txt"));
+    }
+
+    #[test]
+    fn test_read_day() {
+        let inputs = Inputs::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"));
+        let input = inputs
+            .read(&Source::Day {
+                day: 9,
+                example: true,
+            })
+            .unwrap();
+        assert_eq!(input, "2333133121414131402");
+    }
+}
@@ -1,2 +1,9 @@
+pub mod answer;
+pub mod export;
+pub mod input;
+pub mod ordering;
+pub mod parse;
+pub mod search;
 pub mod solutions;
 pub mod types;
+pub mod verify;
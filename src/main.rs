@@ -1,23 +1,544 @@
-use aoc_2024::solutions;
-use std::env;
+mod scaffold;
 
-fn main() -> anyhow::Result<()> {
-    let day: u8 = env::args()
-        .nth(1)
-        .expect("Please provide the day number")
-        .parse()?;
+use anyhow::{anyhow, bail, Result};
+use aoc_2024::{
+    export::Format,
+    input::{Inputs, Source},
+    solutions::{self, day06, day07, Day, Part, Run},
+    types::Point,
+    verify::{AnswerStore, Status},
+};
+use clap::{Args, Parser, Subcommand};
+use std::{
+    io::{self, Write},
+    panic,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
 
+/// Advent of Code 2024 solutions.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Directory containing dayNN.txt inputs [default: $AOC_INPUTS_DIR, ./inputs, or the crate's inputs]
+    #[arg(long, global = true)]
+    inputs_dir: Option<PathBuf>,
+    /// Directory containing dayNN.toml accepted answers [default: $AOC_ANSWERS_DIR, ./answers, or the crate's answers]
+    #[arg(long, global = true)]
+    answers_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day, or every implemented day when no day is given
+    Run {
+        #[arg(value_parser = day_parser())]
+        day: Option<u8>,
+        #[command(flatten)]
+        part: PartArgs,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Also print the day's notes on its input, e.g. the rules each day 5 update breaks
+        #[arg(long, requires = "day")]
+        explain: bool,
+        /// Print the day's input as a Graphviz DOT graph instead, e.g. day 5's
+        /// rules; given an item such as a day 5 update, only that part of it
+        #[arg(
+            long,
+            value_name = "ITEM",
+            num_args = 0..=1,
+            requires = "day",
+            conflicts_with = "explain"
+        )]
+        dot: Option<Option<usize>>,
+    },
+    /// Check a day's answer against an expected value
+    Test {
+        #[arg(value_parser = day_parser())]
+        day: u8,
+        /// The answer the selected part should produce
+        expected: String,
+        /// Part to check
+        #[arg(short, long, default_value_t = 1, value_parser = part_parser())]
+        part: u8,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Time repeated runs of a day, or of every implemented day
+    Bench {
+        #[arg(value_parser = day_parser())]
+        day: Option<u8>,
+        /// Number of runs to time
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: u32,
+        #[command(flatten)]
+        part: PartArgs,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Check the answers for the real inputs against the recorded ones
+    Verify {
+        #[arg(value_parser = day_parser())]
+        day: Option<u8>,
+        /// Record the current answer for parts that have no recorded answer yet
+        #[arg(long)]
+        record: bool,
+    },
+    /// Animate day 6's guard patrol in the terminal
+    Animate {
+        /// Place an extra obstacle at this `x,y` point first
+        #[arg(short, long)]
+        obstacle: Option<Point>,
+        /// Milliseconds to show each frame for
+        #[arg(short, long, default_value_t = 50)]
+        delay: u64,
+        /// Wait for Enter before each frame instead
+        #[arg(short, long, conflicts_with = "delay")]
+        step: bool,
+        #[command(flatten)]
+        rules: RulesArgs,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Export day 6's patrol path or its loop-causing obstacles
+    ExportPatrol {
+        /// What to export: `path` or `obstacles`
+        #[arg(default_value = "path")]
+        data: day06::PatrolData,
+        /// Output format: `json` or `csv`
+        #[arg(short, long, default_value = "json")]
+        format: Format,
+        #[command(flatten)]
+        rules: RulesArgs,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Solve day 7's calibration equations with another set of operators
+    Calibrate {
+        /// Comma-separated operators: `add`, `mul`, `concat`, `concat<base>`,
+        /// `sub`, `div`, `pow` or `xor`
+        #[arg(short, long, value_delimiter = ',', default_value = "add,mul,concat")]
+        operators: Vec<day07::BuiltinOperator>,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Scaffold the module and input files for a new day
+    New {
+        #[arg(value_parser = day_parser())]
+        day: u8,
+    },
+    /// List implemented days
+    List,
+}
+
+#[derive(Args)]
+struct PartArgs {
+    /// Only run this part
+    #[arg(short, long, value_parser = part_parser())]
+    part: Option<u8>,
+}
+
+impl PartArgs {
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![Part::try_from(part).unwrap()],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Read the puzzle input from this file, or from stdin if it is `-`
+    #[arg(short, long, conflicts_with = "example")]
+    input: Option<PathBuf>,
+    /// Use the example input from the examples subdirectory of the inputs directory
+    #[arg(short, long)]
+    example: bool,
+}
+
+impl InputArgs {
+    fn source(&self, day: u8) -> Source {
+        match &self.input {
+            Some(path) => Source::from_arg(path),
+            None => Source::Day {
+                day,
+                example: self.example,
+            },
+        }
+    }
+}
+
+#[derive(Args)]
+struct RulesArgs {
+    /// Which way guards turn at an obstacle: `right`, `left` or `around`
+    #[arg(short, long, default_value = "right")]
+    turn: day06::Turn,
+    /// Bring guards walking off an edge back in on the opposite one
+    #[arg(short, long)]
+    wrap: bool,
+}
+
+impl RulesArgs {
+    fn rules(&self) -> day06::Rules {
+        day06::Rules {
+            turn: self.turn,
+            edges: if self.wrap {
+                day06::Edges::Wrap
+            } else {
+                day06::Edges::Exit
+            },
+        }
+    }
+}
+
+/// Reads the input of any day according to the command-line options.
+struct InputReader<'a> {
+    inputs: &'a Inputs,
+    args: &'a InputArgs,
+}
+
+impl InputReader<'_> {
+    fn read(&self, day: u8) -> Result<String> {
+        self.inputs.read(&self.args.source(day))
+    }
+}
+
+fn day_parser() -> clap::builder::RangedI64ValueParser<u8> {
+    clap::value_parser!(u8).range(1..=25)
+}
+
+fn part_parser() -> clap::builder::RangedI64ValueParser<u8> {
+    clap::value_parser!(u8).range(1..=2)
+}
+
+fn get_day(day: u8) -> Result<&'static Day> {
+    solutions::get(day).ok_or_else(|| {
+        anyhow!(
+            "day {} is not implemented yet (use `list` to see implemented days)",
+            day
+        )
+    })
+}
+
+// Resolves the optional day argument to the days to run, refusing an
+// explicit input file when running more than one day.
+fn select_days(day: Option<u8>, input: &InputReader) -> Result<Vec<&'static Day>> {
     match day {
-        1 => solutions::day01::solve()?,
-        2 => solutions::day02::solve()?,
-        3 => solutions::day03::solve()?,
-        4 => solutions::day04::solve()?,
-        5 => solutions::day05::solve()?,
-        6 => solutions::day06::solve()?,
-        7 => solutions::day07::solve()?,
-        8 => solutions::day08::solve()?,
-        9 => solutions::day09::solve()?,
-        _ => println!("Day {} not implemented yet", day),
+        Some(day) => Ok(vec![get_day(day)?]),
+        None if input.args.input.is_some() => bail!("--input requires a day"),
+        None => Ok(solutions::all().iter().collect()),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+// Runs a day, turning a missing input or a panicking solver into an error
+// message so that a single broken day does not abort the whole table.
+fn try_run(day: &Day, parts: &[Part], input: &InputReader) -> Result<Run, String> {
+    let input = input.read(day.number).map_err(|e| e.to_string())?;
+    panic::catch_unwind(|| day.run(&input, parts))
+        .map_err(|_| "panicked".to_string())?
+        .map_err(|e| e.to_string())
+}
+
+fn run_one(day: &Day, parts: &[Part], input: &InputReader, explain: bool) -> Result<()> {
+    let input = input.read(day.number)?;
+    let run = day.run(&input, parts);
+    if let Ok(run) = &run {
+        for result in &run.results {
+            println!("Part {}: {}", result.part, result.answer);
+        }
+    }
+    // Explain even when solving fails, since that is when the notes help.
+    if explain {
+        let notes = day.explain(&input)?;
+        if notes.is_empty() {
+            println!("Day {} has nothing to explain", day.number);
+        }
+        for note in notes {
+            println!("{}", note);
+        }
+    }
+    run.map(|_| ())
+}
+
+fn print_dot(day: &Day, item: Option<usize>, input: &InputReader) -> Result<()> {
+    let input = input.read(day.number)?;
+    match day.dot(&input, item)? {
+        Some(dot) => print!("{}", dot),
+        None => bail!("day {} has nothing to draw", day.number),
+    }
+    Ok(())
+}
+
+fn run_all(days: &[&Day], parts: &[Part], input: &InputReader) {
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:>10}  {:>10}",
+        "Day", "Part", "Answer", "Parse", "Solve"
+    );
+
+    let mut total = Duration::ZERO;
+    for day in days {
+        match try_run(day, parts, input) {
+            Ok(run) => {
+                for result in &run.results {
+                    println!(
+                        "{:>3}  {:>4}  {:<20}  {:>10}  {:>10}",
+                        day.number,
+                        result.part,
+                        result.answer,
+                        format_duration(run.parse_time),
+                        format_duration(result.time),
+                    );
+                    total += result.time;
+                }
+                total += run.parse_time;
+            }
+            Err(error) => println!("{:>3}  {:>4}  error: {}", day.number, "-", error),
+        }
+    }
+
+    println!("Total: {}", format_duration(total));
+}
+
+fn test(day: &Day, part: Part, expected: &str, input: &InputReader) -> Result<()> {
+    let run = day.run(&input.read(day.number)?, &[part])?;
+    let answer = run.results[0].answer.to_string();
+    if answer != expected {
+        bail!(
+            "day {} part {}: expected {}, got {}",
+            day.number,
+            part,
+            expected,
+            answer
+        );
+    }
+    println!("Day {} part {}: ok ({})", day.number, part, answer);
+    Ok(())
+}
+
+fn verify(days: &[&Day], input: &InputReader, store: &AnswerStore, record: bool) -> Result<()> {
+    let (mut mismatches, mut failures) = (0, 0);
+    for day in days {
+        let mut recorded = store.load(day.number)?;
+        let run = match try_run(day, &Part::ALL, input) {
+            Ok(run) => run,
+            Err(error) => {
+                println!("Day {:>2}: error: {}", day.number, error);
+                failures += 1;
+                continue;
+            }
+        };
+
+        let mut changed = false;
+        for result in run.results {
+            let label = format!("Day {:>2} part {}", day.number, result.part);
+            match Status::of(recorded.get(result.part), &result.answer) {
+                Status::Ok => println!("{}: ok", label),
+                Status::Mismatch { expected } => {
+                    println!(
+                        "{}: MISMATCH (expected {}, got {})",
+                        label, expected, result.answer
+                    );
+                    mismatches += 1;
+                }
+                Status::Unrecorded if record => {
+                    println!("{}: recorded {}", label, result.answer);
+                    recorded.set(result.part, result.answer);
+                    changed = true;
+                }
+                Status::Unrecorded => println!("{}: no recorded answer ({})", label, result.answer),
+            }
+        }
+
+        if changed {
+            store.save(day.number, &recorded)?;
+        }
+    }
+
+    if mismatches > 0 || failures > 0 {
+        bail!(
+            "{} mismatched answers and {} failed days (answers in {})",
+            mismatches,
+            failures,
+            store.dir().display()
+        );
+    }
+    Ok(())
+}
+
+fn bench(days: &[&Day], parts: &[Part], iterations: u32, input: &InputReader) -> Result<()> {
+    if iterations == 0 {
+        bail!("--iterations must be at least 1");
+    }
+
+    println!(
+        "{:>3}  {:>5}  {:>10}  {:>10}  {:>10}",
+        "Day", "Part", "Min", "Mean", "Max"
+    );
+    for day in days {
+        let input = input.read(day.number)?;
+        let runs = (0..iterations)
+            .map(|_| day.run(&input, parts))
+            .collect::<Result<Vec<_>>>()?;
+
+        let rows = std::iter::once((
+            "parse".to_string(),
+            runs.iter().map(|run| run.parse_time).collect::<Vec<_>>(),
+        ))
+        .chain(parts.iter().enumerate().map(|(i, part)| {
+            (
+                part.to_string(),
+                runs.iter().map(|run| run.results[i].time).collect(),
+            )
+        }));
+
+        for (label, times) in rows {
+            let min = times.iter().min().unwrap();
+            let max = times.iter().max().unwrap();
+            let mean = times.iter().sum::<Duration>() / iterations;
+            println!(
+                "{:>3}  {:>5}  {:>10}  {:>10}  {:>10}",
+                day.number,
+                label,
+                format_duration(*min),
+                format_duration(mean),
+                format_duration(*max),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn animate(
+    input: &str,
+    obstacle: Option<Point>,
+    rules: day06::Rules,
+    delay: u64,
+    step: bool,
+) -> Result<()> {
+    let outcomes = day06::animate(input, obstacle, rules, |frame| {
+        // Move the cursor home and clear the screen before drawing.
+        print!("\x1b[H\x1b[2J{}", frame);
+        io::stdout().flush()?;
+        if step {
+            io::stdin().read_line(&mut String::new())?;
+        } else {
+            thread::sleep(Duration::from_millis(delay));
+        }
+        Ok(())
+    })?;
+    for (i, outcome) in outcomes.iter().enumerate() {
+        if *outcome == day06::Outcome::Exit {
+            println!("Guard {} left the map", i + 1);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let inputs = Inputs::resolve(cli.inputs_dir);
+    let reader = |args| InputReader {
+        inputs: &inputs,
+        args,
+    };
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            explain,
+            dot,
+        } => {
+            let parts = part.parts();
+            let input = reader(&input);
+            match (day, dot) {
+                (Some(day), Some(item)) => print_dot(get_day(day)?, item, &input)?,
+                (Some(day), None) => run_one(get_day(day)?, &parts, &input, explain)?,
+                (None, _) => run_all(&select_days(None, &input)?, &parts, &input),
+            }
+        }
+        Command::Test {
+            day,
+            expected,
+            part,
+            input,
+        } => test(
+            get_day(day)?,
+            Part::try_from(part)?,
+            &expected,
+            &reader(&input),
+        )?,
+        Command::Bench {
+            day,
+            iterations,
+            part,
+            input,
+        } => {
+            let input = reader(&input);
+            bench(
+                &select_days(day, &input)?,
+                &part.parts(),
+                iterations,
+                &input,
+            )?
+        }
+        Command::Verify { day, record } => {
+            let args = InputArgs {
+                input: None,
+                example: false,
+            };
+            let input = reader(&args);
+            let store = AnswerStore::resolve(cli.answers_dir);
+            verify(&select_days(day, &input)?, &input, &store, record)?
+        }
+        Command::Animate {
+            obstacle,
+            delay,
+            step,
+            rules,
+            input,
+        } => {
+            // Stepping waits for Enter on stdin, which cannot also be the input.
+            if step && input.source(6) == Source::Stdin {
+                bail!("--step cannot be used with --input -");
+            }
+            animate(
+                &reader(&input).read(6)?,
+                obstacle,
+                rules.rules(),
+                delay,
+                step,
+            )?
+        }
+        Command::ExportPatrol {
+            data,
+            format,
+            rules,
+            input,
+        } => {
+            let table = day06::export(&reader(&input).read(6)?, data, rules.rules())?;
+            print!("{}", table.write(format));
+        }
+        Command::Calibrate { operators, input } => {
+            let input = reader(&input).read(7)?;
+            println!("{}", day07::calibrate(&input, &operators)?);
+        }
+        Command::New { day } => scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day)?,
+        Command::List => {
+            for day in solutions::all() {
+                println!("Day {:02}", day.number);
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,405 @@
+//! Ordering items by "`a` must come before `b`" rules.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    hash::Hash,
+};
+
+/// Directed graph with an edge from `a` to `b` for every rule "`a` before `b`".
+///
+/// The rules need not form a total or even a partial order as a whole;
+/// only the subsets of nodes that are sorted have to be acyclic.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    successors: HashMap<N, Vec<N>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            successors: HashMap::new(),
+        }
+    }
+}
+
+/// Error from `Graph::sort` when the rules between the given nodes contradict
+/// each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N> {
+    /// Nodes of the cycle in rule order; the last must also come before the first.
+    pub nodes: Vec<N>,
+}
+
+impl<N: fmt::Display> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ordering rules form a cycle: ")?;
+        for node in &self.nodes {
+            write!(f, "{} -> ", node)?;
+        }
+        match self.nodes.first() {
+            Some(first) => write!(f, "{}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: fmt::Debug + fmt::Display> std::error::Error for Cycle<N> {}
+
+/// A rule broken by an order of nodes: `before` should come before `after`,
+/// but is found after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation<N> {
+    pub before: N,
+    pub after: N,
+    pub before_index: usize,
+    pub after_index: usize,
+}
+
+impl<N: fmt::Display> fmt::Display for Violation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}: {} at index {} comes before {} at index {}",
+            self.before, self.after, self.after, self.after_index, self.before, self.before_index
+        )
+    }
+}
+
+impl<N: Copy + Hash + Eq> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the graph from a map of each node to the nodes that must come
+    /// before it.
+    pub fn from_predecessors(predecessors: &HashMap<N, Vec<N>>) -> Self {
+        let mut graph = Self::new();
+        for (&after, befores) in predecessors {
+            for &before in befores {
+                graph.add_rule(before, after);
+            }
+        }
+        graph
+    }
+
+    /// Adds the rule that `before` must come before `after`.
+    pub fn add_rule(&mut self, before: N, after: N) {
+        self.successors.entry(before).or_default().push(after);
+    }
+
+    /// Whether there is a rule that `before` must come before `after`.
+    pub fn has_rule(&self, before: N, after: N) -> bool {
+        self.successors
+            .get(&before)
+            .is_some_and(|afters| afters.contains(&after))
+    }
+
+    /// The nodes that must come after `node`.
+    pub fn successors(&self, node: N) -> &[N] {
+        self.successors.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    // The successors of every node in `nodes`, counting only rules between them.
+    fn restrict(&self, nodes: &[N]) -> HashMap<N, Vec<N>> {
+        let set = nodes.iter().collect::<HashSet<_>>();
+        nodes
+            .iter()
+            .map(|&node| {
+                let afters = self.successors(node);
+                (
+                    node,
+                    afters.iter().copied().filter(|n| set.contains(n)).collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Sorts `nodes` so that every rule between two of them is respected,
+    /// using Kahn's algorithm on the rules restricted to `nodes`: rules
+    /// involving other nodes are ignored, even if they imply an order by
+    /// transitivity. Nodes not ordered by any rule keep their relative order,
+    /// and a repeated node is placed after everything that must precede it.
+    pub fn sort(&self, nodes: &[N]) -> Result<Vec<N>, Cycle<N>> {
+        let successors = self.restrict(nodes);
+        // In-degrees are counted per occurrence, so that repeated nodes
+        // become ready together.
+        let mut occurrences = HashMap::<N, Vec<usize>>::new();
+        for (i, &node) in nodes.iter().enumerate() {
+            occurrences.entry(node).or_default().push(i);
+        }
+        let mut in_degree = vec![0; nodes.len()];
+        for &node in nodes {
+            for after in &successors[&node] {
+                for &j in &occurrences[after] {
+                    in_degree[j] += 1;
+                }
+            }
+        }
+
+        let mut ready = (0..nodes.len())
+            .filter(|&i| in_degree[i] == 0)
+            .collect::<VecDeque<_>>();
+        let mut sorted = Vec::with_capacity(nodes.len());
+        while let Some(i) = ready.pop_front() {
+            sorted.push(nodes[i]);
+            for after in &successors[&nodes[i]] {
+                for &j in &occurrences[after] {
+                    in_degree[j] -= 1;
+                    if in_degree[j] == 0 {
+                        ready.push_back(j);
+                    }
+                }
+            }
+        }
+
+        if sorted.len() < nodes.len() {
+            let mut remaining = (0..nodes.len())
+                .filter(|&i| in_degree[i] > 0)
+                .map(|i| nodes[i])
+                .collect::<Vec<_>>();
+            remaining.dedup();
+            return Err(find_cycle(&remaining, &successors));
+        }
+        Ok(sorted)
+    }
+
+    /// Whether `nodes` are in an order respecting every rule between them.
+    pub fn is_sorted(&self, nodes: &[N]) -> bool {
+        self.violations(nodes).is_empty()
+    }
+
+    /// Every rule between two of `nodes` that their order breaks, by the
+    /// position of the node that comes too early, then of the one too late.
+    pub fn violations(&self, nodes: &[N]) -> Vec<Violation<N>> {
+        let mut violations = Vec::new();
+        for (after_index, &after) in nodes.iter().enumerate() {
+            for (before_index, &before) in nodes.iter().enumerate().skip(after_index + 1) {
+                if self.has_rule(before, after) {
+                    violations.push(Violation {
+                        before,
+                        after,
+                        before_index,
+                        after_index,
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+impl<N: Copy + Hash + Ord + fmt::Display> Graph<N> {
+    /// The rules as a Graphviz digraph, with an edge from each node to the
+    /// nodes that must come after it. Given `nodes`, only they and the rules
+    /// between them are included, and rules broken by their order are red.
+    pub fn to_dot(&self, nodes: Option<&[N]>) -> String {
+        let (nodes, broken) = match nodes {
+            Some(nodes) => (
+                nodes.to_vec(),
+                self.violations(nodes)
+                    .iter()
+                    .map(|v| (v.before, v.after))
+                    .collect(),
+            ),
+            None => {
+                let mut all = self
+                    .successors
+                    .iter()
+                    .flat_map(|(&before, afters)| afters.iter().copied().chain([before]))
+                    .collect::<Vec<_>>();
+                all.sort();
+                all.dedup();
+                (all, HashSet::new())
+            }
+        };
+        let included = nodes.iter().collect::<HashSet<_>>();
+
+        let mut dot = String::from("digraph rules {\n");
+        for node in &nodes {
+            dot.push_str(&format!("    \"{}\";\n", node));
+        }
+        for &before in &nodes {
+            let mut afters = self
+                .successors(before)
+                .iter()
+                .filter(|after| included.contains(after))
+                .collect::<Vec<_>>();
+            afters.sort();
+            afters.dedup();
+            for &after in afters {
+                let style = if broken.contains(&(before, after)) {
+                    " [color=red, penwidth=2]"
+                } else {
+                    ""
+                };
+                dot.push_str(&format!("    \"{}\" -> \"{}\"{};\n", before, after, style));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// Every node left over by Kahn's algorithm has a predecessor that is also left
+// over, so walking predecessors from any of them must eventually repeat a node.
+fn find_cycle<N: Copy + Hash + Eq>(remaining: &[N], successors: &HashMap<N, Vec<N>>) -> Cycle<N> {
+    if remaining.is_empty() {
+        return Cycle { nodes: Vec::new() };
+    }
+    let set = remaining.iter().collect::<HashSet<_>>();
+    let mut predecessor = HashMap::new();
+    for &before in remaining {
+        for &after in &successors[&before] {
+            if set.contains(&after) {
+                predecessor.entry(after).or_insert(before);
+            }
+        }
+    }
+
+    let mut walk = vec![remaining[0]];
+    let mut seen = HashMap::from([(remaining[0], 0)]);
+    loop {
+        let previous = predecessor[walk.last().unwrap()];
+        if let Some(&start) = seen.get(&previous) {
+            let mut nodes = walk.split_off(start);
+            nodes.reverse();
+            return Cycle { nodes };
+        }
+        seen.insert(previous, walk.len());
+        walk.push(previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(rules: &[(u32, u32)]) -> Graph<u32> {
+        let mut graph = Graph::new();
+        for &(before, after) in rules {
+            graph.add_rule(before, after);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_from_predecessors() {
+        let graph = Graph::from_predecessors(&HashMap::from([(3, vec![1, 2]), (2, vec![1])]));
+        assert!(graph.has_rule(1, 3));
+        assert!(graph.has_rule(2, 3));
+        assert!(graph.has_rule(1, 2));
+        assert!(!graph.has_rule(3, 1));
+    }
+
+    #[test]
+    fn test_sort() {
+        let graph = graph(&[(1, 2), (2, 3), (1, 3), (4, 1)]);
+        assert_eq!(graph.sort(&[3, 2, 1]), Ok(vec![1, 2, 3]));
+        // 4 before 1 before 3, but 1 is not being sorted.
+        assert_eq!(graph.sort(&[3, 4]), Ok(vec![3, 4]));
+        assert!(graph.is_sorted(&[4, 1, 3]));
+        assert!(!graph.is_sorted(&[1, 4, 3]));
+    }
+
+    #[test]
+    fn test_violations() {
+        let graph = graph(&[(1, 2), (2, 3), (1, 3)]);
+        assert_eq!(graph.violations(&[1, 2, 3]), vec![]);
+        assert_eq!(
+            graph.violations(&[3, 1, 2]),
+            vec![
+                Violation {
+                    before: 1,
+                    after: 3,
+                    before_index: 1,
+                    after_index: 0
+                },
+                Violation {
+                    before: 2,
+                    after: 3,
+                    before_index: 2,
+                    after_index: 0
+                },
+            ]
+        );
+        assert_eq!(
+            graph.violations(&[2, 1])[0].to_string(),
+            "1|2: 2 at index 0 comes before 1 at index 1"
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let graph = graph(&[(2, 3), (1, 2), (1, 3), (3, 4)]);
+        assert_eq!(
+            graph.to_dot(None),
+            "digraph rules {
+    \"1\";
+    \"2\";
+    \"3\";
+    \"4\";
+    \"1\" -> \"2\";
+    \"1\" -> \"3\";
+    \"2\" -> \"3\";
+    \"3\" -> \"4\";
+}
+"
+        );
+        assert_eq!(
+            graph.to_dot(Some(&[3, 1])),
+            "digraph rules {
+    \"3\";
+    \"1\";
+    \"1\" -> \"3\" [color=red, penwidth=2];
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_sort_keeps_unrelated_order() {
+        let graph = graph(&[(1, 2)]);
+        assert_eq!(graph.sort(&[5, 2, 7, 1]), Ok(vec![5, 7, 1, 2]));
+        assert_eq!(graph.sort(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_sort_repeated_nodes() {
+        let graph = graph(&[(1, 2), (2, 3)]);
+        assert_eq!(graph.sort(&[1, 2, 2]), Ok(vec![1, 2, 2]));
+        assert_eq!(graph.sort(&[2, 3, 1, 2]), Ok(vec![1, 2, 2, 3]));
+        assert_eq!(graph.sort(&[5, 7, 5]), Ok(vec![5, 7, 5]));
+        assert!(graph.is_sorted(&[1, 2, 2, 3]));
+
+        let graph = self::graph(&[(1, 2), (2, 1)]);
+        assert_eq!(graph.sort(&[1, 2, 1]).unwrap_err().nodes.len(), 2);
+    }
+
+    #[test]
+    fn test_sort_ignores_cycles_outside_nodes() {
+        let graph = graph(&[(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(graph.sort(&[3, 1]), Ok(vec![3, 1]));
+        assert_eq!(graph.sort(&[2, 1]), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_sort_cycle() {
+        let graph = graph(&[(1, 2), (2, 3), (3, 1), (0, 1), (3, 4)]);
+        let cycle = graph.sort(&[4, 3, 2, 1, 0]).unwrap_err();
+        assert_eq!(cycle.nodes.len(), 3);
+        let first = cycle.nodes[0];
+        assert!(cycle
+            .nodes
+            .iter()
+            .zip(cycle.nodes.iter().cycle().skip(1))
+            .all(|(&a, &b)| graph.has_rule(a, b)));
+        assert_eq!(
+            cycle.to_string(),
+            format!(
+                "ordering rules form a cycle: {} -> {} -> {} -> {}",
+                first, cycle.nodes[1], cycle.nodes[2], first
+            )
+        );
+    }
+}
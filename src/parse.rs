@@ -0,0 +1,199 @@
+use crate::types::{Grid, GridErrorKind};
+use std::{error::Error, fmt, str::FromStr};
+
+/// Characters of context kept on each side of the error in `snippet`.
+const CONTEXT: usize = 30;
+
+/// A malformed puzzle input, pointing at the offending line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The offending line, shortened around `column` if it is long.
+    pub snippet: String,
+    pub expected: String,
+    // Position of `column` within `snippet`, in characters.
+    caret: usize,
+}
+
+impl ParseError {
+    pub fn new(
+        day: u8,
+        line: usize,
+        text: &str,
+        column: usize,
+        expected: impl Into<String>,
+    ) -> Self {
+        let chars = text.chars().collect::<Vec<_>>();
+        let start = column.saturating_sub(CONTEXT + 1);
+        let end = (column + CONTEXT).min(chars.len());
+        let mut snippet = chars[start.min(end)..end].iter().collect::<String>();
+        let mut caret = column - 1 - start;
+        if start > 0 {
+            snippet.insert_str(0, "...");
+            caret += 3;
+        }
+        if end < chars.len() {
+            snippet.push_str("...");
+        }
+        Self {
+            day,
+            line,
+            column,
+            snippet,
+            expected: expected.into(),
+            caret,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "day {}, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        writeln!(f, "  {}", self.snippet)?;
+        write!(f, "  {}^", " ".repeat(self.caret))
+    }
+}
+
+impl Error for ParseError {}
+
+/// One line of a day's input, used to report errors at their position.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub day: u8,
+    /// 1-based line number.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Reports an error at `token`, which must be a slice of `self.text`.
+    pub fn error_at(&self, token: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|&offset| offset <= self.text.len())
+            .unwrap_or(0);
+        self.error_at_offset(offset, expected)
+    }
+
+    /// Reports an error at byte `offset` of `self.text`.
+    pub fn error_at_offset(&self, offset: usize, expected: impl Into<String>) -> ParseError {
+        let column = self.text[..offset].chars().count() + 1;
+        ParseError::new(self.day, self.number, self.text, column, expected)
+    }
+
+    /// Reports an error just past the end of the line.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at_offset(self.text.len(), expected)
+    }
+
+    /// Parses `token`, a slice of `self.text`, reporting its position on failure.
+    pub fn parse<T: FromStr>(&self, token: &'a str, expected: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error_at(token, expected))
+    }
+
+    pub fn split_once(&self, delimiter: char) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error_at_end(format!("`{}`", delimiter)))
+    }
+}
+
+/// Splits `input` into numbered lines for `day`.
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(i, text)| Line {
+        day,
+        number: i + 1,
+        text,
+    })
+}
+
+/// Parses a rectangular grid for `day`, mapping each character with `tile`.
+pub fn grid<T>(
+    day: u8,
+    input: &str,
+    tile: impl Fn(char) -> Option<T>,
+    expected: &str,
+) -> Result<Grid<T>, ParseError> {
+    Grid::from_str(input, tile).map_err(|error| {
+        let text = input.lines().nth(error.line - 1).unwrap_or("");
+        let expected = match error.kind {
+            GridErrorKind::InvalidChar => expected.to_string(),
+            GridErrorKind::RaggedRow { width } => format!("a row of width {}", width),
+            GridErrorKind::Empty => "a non-empty grid".to_string(),
+        };
+        ParseError::new(day, error.line, text, error.column, expected)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_position() {
+        let line = Line {
+            day: 1,
+            number: 3,
+            text: "12 x4",
+        };
+        let error = line.parse::<u32>(&line.text[3..], "a number").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 3, column 4: expected a number\n  12 x4\n     ^"
+        );
+    }
+
+    #[test]
+    fn test_long_line_snippet() {
+        let text = "1".repeat(100) + "x" + &"1".repeat(100);
+        let line = Line {
+            day: 9,
+            number: 1,
+            text: &text,
+        };
+        let error = line.error_at_offset(100, "a digit");
+        assert_eq!(error.column, 101);
+        assert_eq!(error.snippet.len(), 3 + 61 + 3);
+        let rendered = error.to_string();
+        let caret = rendered.lines().last().unwrap().find('^').unwrap();
+        assert_eq!(rendered.lines().nth(1).unwrap().as_bytes()[caret], b'x');
+    }
+
+    #[test]
+    fn test_split_once() {
+        let line = Line {
+            day: 5,
+            number: 1,
+            text: "47-53",
+        };
+        let error = line.split_once('|').unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (6, "`|`"));
+    }
+
+    #[test]
+    fn test_grid() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(
+            grid(4, "12\n34\n", digit, "a digit").unwrap(),
+            Grid::new(2, 2, vec![1, 2, 3, 4])
+        );
+
+        let error = grid(4, "12\n3x\n", digit, "a digit").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = grid(4, "12\n345\n", digit, "a digit").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "a row of width 2");
+
+        assert!(grid(4, "", digit, "a digit").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::{bail, Context, Result};
+use std::{fs, path::Path};
+
+#[cfg(test)]
+use crate::solutions::Solution;
+
+// The template is compiled in tests, so that a change to `Solution` that it
+// does not follow fails the build.
+#[cfg(test)]
+#[allow(dead_code)]
+mod template;
+
+// The module for a new day, written as day 0 so that it compiles as is.
+const TEMPLATE: &str = include_str!("scaffold/template.rs");
+
+/// Creates the module, registry entry and empty input files for a new day.
+pub fn new_day(root: &Path, day: u8) -> Result<()> {
+    let module = format!("day{:02}", day);
+    let source = root.join("src/solutions").join(format!("{}.rs", module));
+    if source.exists() {
+        bail!("{} already exists", source.display());
+    }
+
+    let registry = root.join("src/solutions.rs");
+    let contents = fs::read_to_string(&registry)
+        .with_context(|| format!("failed to read {}", registry.display()))?;
+    let contents = register(&contents, day)?;
+
+    fs::write(&source, render(day))?;
+    fs::write(&registry, contents)?;
+    println!("Created {}", source.display());
+
+    for input in [
+        root.join("inputs").join(format!("{}.txt", module)),
+        root.join("inputs/examples").join(format!("{}.txt", module)),
+    ] {
+        if !input.exists() {
+            fs::create_dir_all(input.parent().unwrap())?;
+            fs::write(&input, "")?;
+            println!("Created {}", input.display());
+        }
+    }
+
+    Ok(())
+}
+
+fn render(day: u8) -> String {
+    TEMPLATE
+        .replace("Day00", &format!("Day{:02}", day))
+        .replace("const DAY: u8 = 0;", &format!("const DAY: u8 = {};", day))
+}
+
+// Adds the `mod` declaration and the `DAYS` entry for `day` to the contents
+// of `solutions.rs`, keeping both lists sorted.
+fn register(contents: &str, day: u8) -> Result<String> {
+    let module = format!("day{:02}", day);
+    let declaration = format!("pub mod {};", module);
+    let entry = format!("    Day::new::<{}::Day{:02}>(),", module, day);
+
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+
+    let mods = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod day"))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let Some(&last_mod) = mods.last() else {
+        bail!("no day modules found in solutions.rs");
+    };
+    let at = mods
+        .iter()
+        .find(|&&i| lines[i].as_str() > declaration.as_str())
+        .copied()
+        .unwrap_or(last_mod + 1);
+    lines.insert(at, declaration);
+
+    let Some(start) = lines.iter().position(|line| line.starts_with("const DAYS")) else {
+        bail!("no DAYS registry found in solutions.rs");
+    };
+    let Some(end) = lines[start..].iter().position(|line| line == "];") else {
+        bail!("unterminated DAYS registry in solutions.rs");
+    };
+    let at = (start + 1..start + end)
+        .find(|&i| lines[i].as_str() > entry.as_str())
+        .unwrap_or(start + end);
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "\
+pub mod day01;
+pub mod day03;
+
+const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(),
+    Day::new::<day03::Day03>(),
+];
+";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(REGISTRY, 2).unwrap(),
+            "\
+pub mod day01;
+pub mod day02;
+pub mod day03;
+
+const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(),
+    Day::new::<day02::Day02>(),
+    Day::new::<day03::Day03>(),
+];
+"
+        );
+    }
+
+    #[test]
+    fn test_register_last() {
+        let registry = register(REGISTRY, 10).unwrap();
+        assert!(registry.contains("pub mod day03;\npub mod day10;\n"));
+        assert!(registry.contains("    Day::new::<day10::Day10>(),\n];"));
+    }
+
+    #[test]
+    fn test_render() {
+        let code = render(10);
+        assert!(code.contains("pub struct Day10;\n"));
+        assert!(code.contains("impl Solution for Day10 {\n    const DAY: u8 = 10;\n"));
+        assert!(!code.contains("Day00"));
+        assert_eq!(template::Day00::DAY, 0);
+    }
+}
@@ -0,0 +1,55 @@
+use super::Solution;
+
+pub struct Day00;
+
+impl Solution for Day00 {
+    const DAY: u8 = 0;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        solve_part2(input)
+    }
+}
+
+fn parse_input(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+fn solve_part1(_lines: &[String]) -> usize {
+    todo!()
+}
+
+fn solve_part2(_lines: &[String]) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "\
+";
+
+    #[test]
+    #[ignore = "expected answer not filled in yet"]
+    fn test_part1() {
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT)), 0);
+    }
+
+    #[test]
+    #[ignore = "expected answer not filled in yet"]
+    fn test_part2() {
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT)), 0);
+    }
+}
@@ -0,0 +1,382 @@
+//! Generic graph searches over states whose neighbors are given by a closure.
+//!
+//! States can be anything `Clone + Hash + Eq`: a `Point`, a `(Point,
+//! Direction)` pair, or a whole puzzle configuration.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Everything a search learned about the states reachable from its start.
+#[derive(Debug, Clone)]
+pub struct Paths<S, C = usize> {
+    costs: HashMap<S, C>,
+    predecessors: HashMap<S, S>,
+    order: Vec<S>,
+}
+
+impl<S: Clone + Hash + Eq, C: Copy> Paths<S, C> {
+    fn new() -> Self {
+        Self {
+            costs: HashMap::new(),
+            predecessors: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    // Records `state` as reached for `cost` via `from`, unless it already was.
+    fn reach(&mut self, state: &S, cost: C, from: Option<&S>) -> bool {
+        if self.costs.contains_key(state) {
+            return false;
+        }
+        self.costs.insert(state.clone(), cost);
+        if let Some(from) = from {
+            self.predecessors.insert(state.clone(), from.clone());
+        }
+        self.order.push(state.clone());
+        true
+    }
+
+    // Records `state` as settled for `cost` via `from`, replacing what an
+    // earlier, costlier settling recorded but keeping its place in the order.
+    fn settle(&mut self, state: &S, cost: C, from: Option<&S>) {
+        if self.costs.insert(state.clone(), cost).is_none() {
+            self.order.push(state.clone());
+        }
+        match from {
+            Some(from) => self.predecessors.insert(state.clone(), from.clone()),
+            None => self.predecessors.remove(state),
+        };
+    }
+
+    /// The cost of the path found to `state`, if it was reached.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.costs.contains_key(state)
+    }
+
+    /// The cost of every reached state.
+    pub fn costs(&self) -> &HashMap<S, C> {
+        &self.costs
+    }
+
+    /// The state each reached state was reached from; the start has none.
+    pub fn predecessors(&self) -> &HashMap<S, S> {
+        &self.predecessors
+    }
+
+    /// The reached states in the order the search settled them.
+    pub fn order(&self) -> &[S] {
+        &self.order
+    }
+
+    /// The states from the start to `goal`, both included, if it was reached.
+    pub fn path_to(&self, goal: &S) -> Option<Vec<S>> {
+        if !self.contains(goal) {
+            return None;
+        }
+        let mut path = vec![goal.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search: the cost of a state is its number of steps from
+/// `start`, and paths are shortest in steps.
+pub fn bfs<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> Paths<S>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new();
+    paths.reach(&start, 0, None);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in neighbors(&state) {
+            if paths.reach(&next, steps + 1, Some(&state)) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    paths
+}
+
+/// Depth-first search: the cost of a state is its depth in the search tree,
+/// which is not in general the length of a shortest path.
+pub fn dfs<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> Paths<S>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new();
+    let mut stack = vec![(start, None, 0)];
+    while let Some((state, from, depth)) = stack.pop() {
+        if !paths.reach(&state, depth, from.as_ref()) {
+            continue;
+        }
+        for next in neighbors(&state) {
+            if !paths.contains(&next) {
+                stack.push((next, Some(state.clone()), depth + 1));
+            }
+        }
+    }
+    paths
+}
+
+// A state waiting in the frontier, ordered so that `BinaryHeap` pops the
+// lowest priority first.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+    from: Option<S>,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+// Best-first search shared by Dijkstra and A*, stopping early once a state
+// satisfying `goal` is settled. A settled state is reopened if a cheaper path
+// to it turns up later, which only happens with an inconsistent heuristic.
+fn best_first<S, C, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Paths<S, C>, Option<S>)
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut paths = Paths::new();
+    let mut best = HashMap::from([(start.clone(), C::default())]);
+    let mut frontier = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start,
+        from: None,
+    }]);
+
+    while let Some(Entry {
+        cost, state, from, ..
+    }) = frontier.pop()
+    {
+        // Skip entries superseded by a cheaper one for the same state.
+        if best.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        paths.settle(&state, cost, from.as_ref());
+        if goal(&state) {
+            return (paths, Some(state));
+        }
+        for (next, step) in neighbors(&state) {
+            let cost = cost + step;
+            if best.get(&next).is_some_and(|&best| best <= cost) {
+                continue;
+            }
+            best.insert(next.clone(), cost);
+            frontier.push(Entry {
+                priority: cost + heuristic(&next),
+                cost,
+                state: next,
+                from: Some(state.clone()),
+            });
+        }
+    }
+    (paths, None)
+}
+
+/// Dijkstra's algorithm: the cheapest cost of every state reachable from
+/// `start`, where `neighbors` yields each next state with the (non-negative)
+/// cost of the step to it.
+pub fn dijkstra<S, C, I>(start: S, neighbors: impl FnMut(&S) -> I) -> Paths<S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first(start, neighbors, |_| C::default(), |_| false).0
+}
+
+/// A* search for the cheapest path from `start` to any state satisfying
+/// `goal`, returning its cost and states. `heuristic` must never overestimate
+/// the remaining cost, e.g. the Manhattan distance on a grid. It need not be
+/// consistent: states are searched again when a cheaper path to them is found.
+pub fn astar<S, C, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (paths, end) = best_first(start, neighbors, heuristic, goal);
+    let end = end?;
+    Some((paths.cost(&end)?, paths.path_to(&end)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Direction, Grid, Point};
+
+    const MAP: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    fn map() -> Grid<char> {
+        Grid::from_str(MAP, Some).unwrap()
+    }
+
+    fn open_neighbors(map: &Grid<char>, point: &Point) -> Vec<Point> {
+        point
+            .neighbors4()
+            .filter(|p| map.get(p).is_some_and(|&c| c != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let map = map();
+        let start = map.find(|&c| c == '^').unwrap();
+        let paths = bfs(start, |p| open_neighbors(&map, p));
+
+        assert_eq!(paths.costs().len(), 100 - 8);
+        assert_eq!(paths.cost(&start), Some(0));
+        assert_eq!(paths.cost(&Point::new(0, 0)), Some(10));
+        assert_eq!(paths.cost(&Point::new(4, 0)), None);
+        assert_eq!(paths.order()[0], start);
+
+        let path = paths.path_to(&Point::new(0, 0)).unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!((path[0], path[10]), (start, Point::new(0, 0)));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let map = map();
+        let start = map.find(|&c| c == '^').unwrap();
+        let paths = dfs(start, |p| open_neighbors(&map, p));
+
+        assert_eq!(paths.costs().len(), 100 - 8);
+        let path = paths.path_to(&Point::new(9, 9)).unwrap();
+        assert_eq!(path.len(), paths.cost(&Point::new(9, 9)).unwrap() + 1);
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    }
+
+    // Walking forward costs 1 and turning costs 1000, so the state is the
+    // position together with the heading.
+    fn turning_neighbors(
+        map: &Grid<char>,
+        &(point, dir): &(Point, Direction),
+    ) -> Vec<((Point, Direction), u32)> {
+        let mut next = vec![
+            ((point, dir.turn_left()), 1000),
+            ((point, dir.turn_right()), 1000),
+        ];
+        let ahead = point + dir.to_vector();
+        if map.get(&ahead).is_some_and(|&c| c != '#') {
+            next.push(((ahead, dir), 1));
+        }
+        next
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let map = map();
+        let start = (map.find(|&c| c == '^').unwrap(), Direction::North);
+        let paths = dijkstra(start, |state| turning_neighbors(&map, state));
+
+        assert_eq!(paths.cost(&(Point::new(4, 0), Direction::North)), None);
+        assert_eq!(paths.cost(&(Point::new(4, 1), Direction::North)), Some(5));
+        assert_eq!(
+            paths.cost(&(Point::new(4, 6), Direction::South)),
+            Some(2000)
+        );
+        assert_eq!(paths.cost(&(Point::new(9, 6), Direction::East)), Some(1005));
+
+        let path = paths.path_to(&(Point::new(9, 6), Direction::East)).unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.len(), 7);
+    }
+
+    #[test]
+    fn test_astar() {
+        let map = map();
+        let start = map.find(|&c| c == '^').unwrap();
+        let goal = Point::new(9, 0);
+        let neighbors = |p: &Point| open_neighbors(&map, p).into_iter().map(|n| (n, 1));
+
+        let (cost, path) = astar(start, neighbors, |p| p.manhattan(goal), |&p| p == goal).unwrap();
+        assert_eq!(
+            cost,
+            bfs(start, |p| open_neighbors(&map, p)).cost(&goal).unwrap() as isize
+        );
+        assert_eq!(path.len() as isize, cost + 1);
+        assert_eq!(path.last(), Some(&goal));
+
+        assert_eq!(
+            astar(start, neighbors, |_| 0, |&p| p == Point::new(4, 0)),
+            None
+        );
+    }
+
+    // The heuristic never overestimates, but drops by more than the step cost
+    // from 'a' to 'c', so 'c' is first settled through the costlier 'b'.
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        let edges = HashMap::from([
+            ('s', vec![('a', 1), ('b', 1)]),
+            ('a', vec![('c', 1)]),
+            ('b', vec![('c', 2)]),
+            ('c', vec![('g', 3)]),
+        ]);
+        let neighbors = |state: &char| edges.get(state).cloned().unwrap_or_default();
+        let heuristic = |&state: &char| if state == 'a' { 4 } else { 0 };
+
+        assert_eq!(
+            astar('s', neighbors, heuristic, |&state| state == 'g'),
+            Some((5, vec!['s', 'a', 'c', 'g']))
+        );
+    }
+}
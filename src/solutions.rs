@@ -1,3 +1,10 @@
+use crate::{answer::Answer, input::normalize};
+use anyhow::{anyhow, Result};
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -7,3 +14,325 @@ pub mod day06;
 pub mod day07;
 pub mod day08;
 pub mod day09;
+
+/// A single day's puzzle, split into parsing and the two parts.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    /// Parses the puzzle input, failing with a `ParseError` on malformed input.
+    /// Callers pass input that has already been through `input::normalize`.
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Notes on the input for `run --explain`, e.g. why day 5 rejects an
+    /// update. Takes the input unparsed, so that it can also describe input
+    /// that `parse` rejects. Most days have none.
+    fn explain(_input: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// The input drawn as a Graphviz DOT graph for `run --dot`, or only its
+    /// 1-based `item`, e.g. one day 5 update. Like `explain`, takes the input
+    /// unparsed. `None` for days with nothing to draw.
+    fn dot(_input: &str, _item: Option<usize>) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Normalizes and parses `input`, then solves both parts.
+    fn solve(input: &str) -> Result<(Answer, Answer)> {
+        let input = Self::parse(&normalize(input))?;
+        Ok((Self::part1(&input).into(), Self::part2(&input).into()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(anyhow!("part must be 1 or 2, got {}", value)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Part::One => "1",
+            Part::Two => "2",
+        })
+    }
+}
+
+/// Answer to one part together with the time it took to compute.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// Outcome of running the requested parts of a day.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse_time: Duration,
+    pub results: Vec<PartResult>,
+}
+
+/// Type-erased entry in the day registry.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    run: fn(&str, &[Part]) -> Result<Run>,
+    explain: fn(&str) -> Result<Vec<String>>,
+    dot: fn(&str, Option<usize>) -> Result<Option<String>>,
+}
+
+impl Day {
+    const fn new<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            run: run::<S>,
+            explain: explain::<S>,
+            dot: dot::<S>,
+        }
+    }
+
+    /// Normalizes and parses `input` once and returns the timed answers to
+    /// `parts`.
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<Run> {
+        (self.run)(input, parts)
+    }
+
+    /// Normalizes `input` and returns the day's notes on it.
+    pub fn explain(&self, input: &str) -> Result<Vec<String>> {
+        (self.explain)(input)
+    }
+
+    /// Normalizes `input` and draws it, or its `item`, as a DOT graph.
+    pub fn dot(&self, input: &str, item: Option<usize>) -> Result<Option<String>> {
+        (self.dot)(input, item)
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Run> {
+    let (input, parse_time) = timed(|| S::parse(&normalize(input)));
+    let input = input?;
+    let results = parts
+        .iter()
+        .map(|&part| {
+            let (answer, time) = timed(|| match part {
+                Part::One => S::part1(&input).into(),
+                Part::Two => S::part2(&input).into(),
+            });
+            PartResult { part, answer, time }
+        })
+        .collect();
+    Ok(Run {
+        parse_time,
+        results,
+    })
+}
+
+fn explain<S: Solution>(input: &str) -> Result<Vec<String>> {
+    S::explain(&normalize(input))
+}
+
+fn dot<S: Solution>(input: &str, item: Option<usize>) -> Result<Option<String>> {
+    S::dot(&normalize(input), item)
+}
+
+const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(),
+    Day::new::<day02::Day02>(),
+    Day::new::<day03::Day03>(),
+    Day::new::<day04::Day04>(),
+    Day::new::<day05::Day05>(),
+    Day::new::<day06::Day06>(),
+    Day::new::<day07::Day07>(),
+    Day::new::<day08::Day08>(),
+    Day::new::<day09::Day09>(),
+];
+
+/// All implemented days, in order.
+pub fn all() -> &'static [Day] {
+    DAYS
+}
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{Inputs, Source};
+    use std::path::Path;
+
+    #[test]
+    fn test_registry_is_ordered_and_unique() {
+        assert!(all().windows(2).all(|w| w[0].number < w[1].number));
+    }
+
+    #[test]
+    fn test_run_selected_part() {
+        let run = get(1).unwrap().run("3   4\n4   3\n", &[Part::Two]).unwrap();
+        assert_eq!(run.results.len(), 1);
+        assert_eq!(run.results[0].part, Part::Two);
+        assert_eq!(run.results[0].answer, Answer::Int(7));
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            get(1).unwrap().explain("3   4\n").unwrap(),
+            Vec::<String>::new()
+        );
+        assert!(get(5).unwrap().explain("1|2\n\n2,1\n").unwrap()[0].starts_with("update 1"));
+    }
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(get(1).unwrap().dot("3   4\n", None).unwrap(), None);
+        let dot = get(5).unwrap().dot("1|2\r\n\r\n2,1\r\n", Some(1)).unwrap();
+        assert!(dot
+            .unwrap()
+            .contains("\"1\" -> \"2\" [color=red, penwidth=2];"));
+    }
+
+    #[test]
+    fn test_part_try_from() {
+        assert_eq!(Part::try_from(1).unwrap(), Part::One);
+        assert_eq!(Part::try_from(2).unwrap(), Part::Two);
+        assert!(Part::try_from(3).is_err());
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            day01::Day01::solve("3   4\n4   3\n").unwrap(),
+            (Answer::Int(0), Answer::Int(7))
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = get(1)
+            .unwrap()
+            .run("3   4\n4   x\n", &Part::ALL)
+            .unwrap_err();
+        let error = error.downcast::<crate::parse::ParseError>().unwrap();
+        assert_eq!((error.day, error.line, error.column), (1, 2, 5));
+    }
+
+    // Every day must give the same answers for its example whether or not the
+    // file has a byte order mark, CRLF line endings or trailing whitespace.
+    // Days just created by `new` have an empty example and are skipped.
+    #[test]
+    fn test_examples_tolerate_line_endings_and_whitespace() {
+        let inputs = Inputs::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs"));
+        for day in all() {
+            let example = inputs
+                .read(&Source::Day {
+                    day: day.number,
+                    example: true,
+                })
+                .unwrap();
+            if example.trim().is_empty() {
+                continue;
+            }
+            let answers = |input: &str| {
+                day.run(input, &Part::ALL)
+                    .unwrap_or_else(|e| panic!("day {}: {}", day.number, e))
+                    .results
+                    .into_iter()
+                    .map(|result| result.answer)
+                    .collect::<Vec<_>>()
+            };
+            let expected = answers(&example);
+
+            let trimmed = example.trim_end();
+            let crlf = example.replace('\n', "\r\n");
+            let variants = [
+                trimmed.to_string(),
+                format!("{}\n", trimmed),
+                format!("{}\n\n\n", trimmed),
+                format!("\u{feff}{}", example),
+                crlf.clone(),
+                format!("\u{feff}{}\r\n\r\n", crlf.trim_end()),
+                example
+                    .lines()
+                    .map(|line| format!("{} \t\n", line))
+                    .collect(),
+            ];
+            for variant in variants {
+                assert_eq!(
+                    answers(&variant),
+                    expected,
+                    "day {}: {:?}",
+                    day.number,
+                    variant
+                );
+            }
+        }
+    }
+
+    // Files saved by other editors, kept byte for byte under
+    // `inputs/examples/normalize`.
+    #[test]
+    fn test_normalize_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs/examples");
+        let inputs = Inputs::new(dir.parent().unwrap());
+        let answers = |day: u8, source: &Source| {
+            let input = inputs.read(source).unwrap();
+            let run = get(day).unwrap().run(&input, &Part::ALL).unwrap();
+            run.results
+                .into_iter()
+                .map(|result| result.answer)
+                .collect::<Vec<_>>()
+        };
+
+        for (day, fixture) in [
+            (1, "day01_bom.txt"),
+            (1, "day01_crlf.txt"),
+            (1, "day01_trailing.txt"),
+            (9, "day09_bom_crlf.txt"),
+        ] {
+            assert_eq!(
+                answers(day, &Source::File(dir.join("normalize").join(fixture))),
+                answers(day, &Source::Day { day, example: true }),
+                "{}",
+                fixture
+            );
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get(9).map(|d| d.number), Some(9));
+        assert!(get(26).is_none());
+    }
+}
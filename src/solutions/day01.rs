@@ -1,49 +1,59 @@
+use super::Solution;
+use crate::parse::{self, ParseError};
 use anyhow::Result;
 use itertools::Itertools;
-use std::fs;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day01.txt")?;
+pub struct Day01;
 
-    let (list1, list2) = parse_input(&input);
+impl Solution for Day01 {
+    const DAY: u8 = 1;
 
-    // Part 1
-    let result1 = solve_part1((list1.clone(), list2.clone()));
-    println!("Part 1: {}", result1);
+    type Input = (Vec<u32>, Vec<u32>);
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    // Part 2
-    let result2 = solve_part2((list1, list2));
-    println!("Part 2: {}", result2);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> u32 {
+        solve_part1(input)
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input) -> u32 {
+        solve_part2(input)
+    }
 }
 
 // Parse the input into two lists of integers
-fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
-    let lists = input
-        .lines()
+fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
+    let pairs = parse::lines(Day01::DAY, input)
         .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse::<u32>().unwrap())
-                .collect::<Vec<u32>>()
+            let mut tokens = line.text.split_whitespace();
+            let mut next = || {
+                let token = tokens.next().ok_or_else(|| line.error_at_end("a number"))?;
+                line.parse::<u32>(token, "a number")
+            };
+            let pair = (next()?, next()?);
+            match tokens.next() {
+                Some(token) => Err(line.error_at(token, "end of line")),
+                None => Ok(pair),
+            }
         })
-        .collect::<Vec<Vec<u32>>>();
-    let lists: Vec<Vec<u32>> = (0..2)
-        .map(|i| lists.iter().map(|list| list[i]).collect::<Vec<u32>>())
-        .collect::<Vec<Vec<u32>>>();
-    (lists[0].clone(), lists[1].clone())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(pairs.into_iter().unzip())
 }
 
-fn solve_part1((list1, list2): (Vec<u32>, Vec<u32>)) -> u32 {
+fn solve_part1((list1, list2): &(Vec<u32>, Vec<u32>)) -> u32 {
     list1
-        .into_iter()
+        .iter()
         .sorted()
-        .zip(list2.into_iter().sorted())
-        .map(|(a, b)| a.abs_diff(b))
+        .zip(list2.iter().sorted())
+        .map(|(a, b)| a.abs_diff(*b))
         .sum()
 }
 
-fn solve_part2((list1, list2): (Vec<u32>, Vec<u32>)) -> u32 {
+fn solve_part2((list1, list2): &(Vec<u32>, Vec<u32>)) -> u32 {
     list1
         .iter()
         .map(|a| list2.iter().filter(|b| a == *b).sum::<u32>())
@@ -63,13 +73,25 @@ mod tests {
 3   3
 ";
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("3   4\n4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = parse_input("3   4\n4   3   5\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.expected.as_str()),
+            (2, 9, "end of line")
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(parse_input(TEST_INPUT)), 11);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 11);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(parse_input(TEST_INPUT)), 31);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT).unwrap()), 31);
     }
 }
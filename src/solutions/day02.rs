@@ -1,29 +1,36 @@
+use super::Solution;
+use crate::parse::{self, ParseError};
 use anyhow::Result;
-use std::fs;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day02.txt")?;
+pub struct Day02;
 
-    let lines = parse_input(&input);
+impl Solution for Day02 {
+    const DAY: u8 = 2;
 
-    // Part 1
-    let result1 = solve_part1(lines.clone());
-    println!("Part 1: {}", result1);
+    type Input = Vec<Vec<u32>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    // Part 2
-    let result2 = solve_part2(lines.clone());
-    println!("Part 2: {}", result2);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        solve_part1(input)
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input) -> usize {
+        solve_part2(input)
+    }
 }
 
 // Parse the input into a list of lists of integers
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
+fn parse_input(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    parse::lines(Day02::DAY, input)
         .map(|line| {
-            line.split_whitespace()
-                .map(|s| s.parse::<u32>().unwrap())
+            line.text
+                .split_whitespace()
+                .map(|s| line.parse::<u32>(s, "a level"))
                 .collect()
         })
         .collect()
@@ -38,24 +45,24 @@ fn validate_with_comparator(line: &[u32], comparator: fn(u32, u32) -> bool) -> b
         .all(|window| validate_distance(window[0], window[1], comparator))
 }
 
-fn validate_safety(line: &Vec<u32>) -> bool {
+fn validate_safety(line: &[u32]) -> bool {
     let validate = |comparator| validate_with_comparator(line, comparator);
     validate(|a, b| a < b) || validate(|a, b| a > b)
 }
 
-fn validate_safety_with_tolerance(line: &Vec<u32>) -> bool {
+fn validate_safety_with_tolerance(line: &[u32]) -> bool {
     (0..line.len()).any(|i| {
-        let mut line = line.clone();
+        let mut line = line.to_vec();
         line.remove(i);
         validate_safety(&line)
     })
 }
 
-fn solve_part1(lines: Vec<Vec<u32>>) -> usize {
-    lines.into_iter().filter(validate_safety).count()
+fn solve_part1(lines: &[Vec<u32>]) -> usize {
+    lines.iter().filter(|line| validate_safety(line)).count()
 }
 
-fn solve_part2(lines: Vec<Vec<u32>>) -> usize {
+fn solve_part2(lines: &[Vec<u32>]) -> usize {
     lines
         .iter()
         .filter(|line| validate_safety_with_tolerance(line))
@@ -63,6 +70,7 @@ fn solve_part2(lines: Vec<Vec<u32>>) -> usize {
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
 
@@ -116,13 +124,19 @@ mod tests {
         ]));
     }
 
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("1 2 3\n4 -5 6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(parse_input(TEST_INPUT)), 2);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 2);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(parse_input(TEST_INPUT)), 4);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT).unwrap()), 4);
     }
 }
@@ -1,20 +1,28 @@
+use super::Solution;
 use anyhow::Result;
 use itertools::Itertools;
 use regex::{Match, Regex};
-use std::fs;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day03.txt")?;
+pub struct Day03;
 
-    // Part 1
-    let result1 = solve_part1(&input);
-    println!("Part 1: {}", result1);
+impl Solution for Day03 {
+    const DAY: u8 = 3;
 
-    // Part 2
-    let result2 = solve_part2(&input);
-    println!("Part 2: {}", result2);
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> u32 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> u32 {
+        solve_part2(input)
+    }
 }
 
 struct State {
@@ -33,6 +41,7 @@ fn solve_part1(input: &str) -> u32 {
         .sum()
 }
 
+#[allow(clippy::map_flatten)]
 fn solve_part2(input: &str) -> u32 {
     [r"mul\((\d{1,3}),(\d{1,3})\)", r"do\(\)", r"don't\(\)"]
         .into_iter()
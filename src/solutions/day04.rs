@@ -1,85 +1,73 @@
-use crate::types::{Point, Vector};
+use super::Solution;
+use crate::parse::{self, ParseError};
+use crate::types::{Direction8, Grid, Point, Vector};
 use anyhow::Result;
-use itertools::Itertools;
-use std::fs;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day04.txt")?;
+pub struct Day04;
 
-    // Part 1
-    let result1 = solve_part1(&input);
-    println!("Part 1: {}", result1);
+impl Solution for Day04 {
+    const DAY: u8 = 4;
 
-    // Part 2
-    let result2 = solve_part2(&input);
-    println!("Part 2: {}", result2);
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Ok(())
-}
-
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-const XMAS: &str = "XMAS";
+    fn part1(input: &Self::Input) -> usize {
+        solve_part1(input)
+    }
 
-fn is_in_bounds(matrix: &[Vec<char>], point: &Point) -> bool {
-    point.x >= 0
-        && point.x < matrix[0].len() as isize
-        && point.y >= 0
-        && point.y < matrix.len() as isize
+    fn part2(input: &Self::Input) -> usize {
+        solve_part2(input)
+    }
 }
 
-fn get_char(matrix: &[Vec<char>], point: &Point) -> char {
-    matrix[point.y as usize][point.x as usize]
+fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    parse::grid(
+        Day04::DAY,
+        input,
+        |c| c.is_ascii_alphabetic().then_some(c),
+        "a letter",
+    )
 }
 
-fn test_char(matrix: &[Vec<char>], point: &Point, expected: char) -> bool {
-    is_in_bounds(matrix, point) && matrix[point.y as usize][point.x as usize] == expected
+const XMAS: &str = "XMAS";
+
+fn test_char(matrix: &Grid<char>, point: &Point, expected: char) -> bool {
+    matrix.get(point) == Some(&expected)
 }
 
-fn find_xmas_in_direction(matrix: &[Vec<char>], point: &Point, direction: &Vector) -> bool {
-    (0..XMAS.len()).all(|i| {
-        let current_point = point + direction * i;
-        test_char(matrix, &current_point, XMAS.chars().nth(i).unwrap())
-    })
+fn find_xmas_in_direction(matrix: &Grid<char>, point: &Point, direction: &Vector) -> bool {
+    point
+        .ray(*direction)
+        .zip(XMAS.chars())
+        .all(|(point, c)| test_char(matrix, &point, c))
 }
 
-const DIRECTIONS: [Vector; 8] = [
-    Vector { dx: 0, dy: -1 },  // North
-    Vector { dx: 1, dy: -1 },  // Northeast
-    Vector { dx: 1, dy: 0 },   // East
-    Vector { dx: 1, dy: 1 },   // Southeast
-    Vector { dx: 0, dy: 1 },   // South
-    Vector { dx: -1, dy: 1 },  // Southwest
-    Vector { dx: -1, dy: 0 },  // West
-    Vector { dx: -1, dy: -1 }, // Northwest
-];
-
-fn find_xmas_in_all_directions(matrix: &[Vec<char>], point: &Point) -> usize {
-    DIRECTIONS
-        .iter()
-        .filter(|direction| find_xmas_in_direction(matrix, point, direction))
+fn find_xmas_in_all_directions(matrix: &Grid<char>, point: &Point) -> usize {
+    Direction8::iter()
+        .filter(|direction| find_xmas_in_direction(matrix, point, &direction.to_vector()))
         .count()
 }
 
-fn test_mas(matrix: &[Vec<char>], points: &[Point; 2]) -> bool {
-    let s = points
-        .iter()
-        .map(|point| get_char(matrix, point))
-        .collect::<String>();
+fn test_mas(matrix: &Grid<char>, points: &[Point; 2]) -> bool {
+    let s = points.iter().map(|point| matrix[point]).collect::<String>();
     s == "MS" || s == "SM"
 }
 
-fn is_x_mas(matrix: &[Vec<char>], point: &Point) -> bool {
-    let top_left = point + Vector { dx: -1, dy: -1 };
-    let top_right = point + Vector { dx: 1, dy: -1 };
-    let bottom_left = point + Vector { dx: -1, dy: 1 };
-    let bottom_right = point + Vector { dx: 1, dy: 1 };
+fn is_x_mas(matrix: &Grid<char>, point: &Point) -> bool {
+    let top_left = point + Direction8::NorthWest.to_vector();
+    let top_right = point + Direction8::NorthEast.to_vector();
+    let bottom_left = point + Direction8::SouthWest.to_vector();
+    let bottom_right = point + Direction8::SouthEast.to_vector();
 
+    let bounds = matrix.bounds();
     if ![&top_left, &top_right, &bottom_left, &bottom_right]
         .iter()
-        .all(|point| is_in_bounds(matrix, point))
+        .all(|point| bounds.contains(point))
     {
         return false;
     }
@@ -87,28 +75,16 @@ fn is_x_mas(matrix: &[Vec<char>], point: &Point) -> bool {
     test_mas(matrix, &[top_left, bottom_right]) && test_mas(matrix, &[top_right, bottom_left])
 }
 
-fn solve_part1(input: &str) -> usize {
-    let matrix = parse_input(input);
-    let find = |point: Point| find_xmas_in_all_directions(&matrix, &point);
+fn solve_part1(matrix: &Grid<char>) -> usize {
+    let find = |point: Point| find_xmas_in_all_directions(matrix, &point);
 
-    (0..matrix.len())
-        .cartesian_product(0..matrix[0].len())
-        .map(Point::new)
-        .map(find)
-        .sum()
+    matrix.points().map(find).sum()
 }
 
-fn solve_part2(input: &str) -> usize {
-    let matrix = parse_input(input);
-
-    (0..matrix.len())
-        .cartesian_product(0..matrix[0].len())
-        .map(Point::new)
-        .filter(|point| {
-            let value = matrix[point.y as usize][point.x as usize];
-            value == 'A'
-        })
-        .filter(|point| is_x_mas(&matrix, point))
+fn solve_part2(matrix: &Grid<char>) -> usize {
+    matrix
+        .positions_of(&'A')
+        .filter(|point| is_x_mas(matrix, point))
         .count()
 }
 
@@ -131,7 +107,7 @@ MXMXAXMASX
 
     #[test]
     fn test_find_xmas_in_direction() {
-        let matrix = parse_input(TEST_INPUT);
+        let matrix = parse_input(TEST_INPUT).unwrap();
 
         assert!(!find_xmas_in_direction(
             &matrix,
@@ -160,7 +136,7 @@ MXMXAXMASX
 
     #[test]
     fn test_find_xmas_in_all_directions() {
-        let matrix = parse_input(TEST_INPUT);
+        let matrix = parse_input(TEST_INPUT).unwrap();
 
         assert_eq!(
             find_xmas_in_all_directions(&matrix, &Point { x: 6, y: 4 }),
@@ -170,11 +146,11 @@ MXMXAXMASX
 
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(TEST_INPUT), 18);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 18);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(TEST_INPUT), 9);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT).unwrap()), 9);
     }
 }
@@ -1,85 +1,170 @@
-use anyhow::Result;
+use super::Solution;
+use crate::ordering::Graph;
+use crate::parse::{self, ParseError};
+use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
-use std::{cmp::Ordering, collections::HashMap, fs};
+use std::collections::HashMap;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day05.txt")?;
+pub struct Day05;
 
-    // Part 1
-    let result1 = solve_part1(&input);
-    println!("Part 1: {}", result1);
+/// Maps each page to the pages that must come before it.
+type Rules = HashMap<usize, Vec<usize>>;
 
-    // Part 2
-    let result2 = solve_part2(&input);
-    println!("Part 2: {}", result2);
+impl Solution for Day05 {
+    const DAY: u8 = 5;
 
-    Ok(())
+    type Input = (Graph<usize>, Vec<Vec<usize>>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (ordering, updates) = parse_input(input)?;
+        let graph = Graph::from_predecessors(&ordering);
+        check_acyclic(&graph, &updates)?;
+        Ok((graph, updates))
+    }
+
+    fn part1((graph, updates): &Self::Input) -> usize {
+        solve_part1(graph, updates)
+    }
+
+    fn part2((graph, updates): &Self::Input) -> usize {
+        solve_part2(graph, updates)
+    }
+
+    fn explain(input: &str) -> Result<Vec<String>> {
+        let (ordering, updates) = parse_input(input)?;
+        Ok(explain_updates(
+            &Graph::from_predecessors(&ordering),
+            &updates,
+        ))
+    }
+
+    fn dot(input: &str, update: Option<usize>) -> Result<Option<String>> {
+        rules_dot(input, update).map(Some)
+    }
 }
 
-fn parse_input(input: &str) -> (HashMap<usize, Vec<usize>>, Vec<Vec<usize>>) {
-    let (ordering, updates) = input.split_once("\n\n").unwrap();
-    let ordering = ordering
-        .lines()
-        .map(|line| line.split_once("|").unwrap())
-        .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
-        .fold(HashMap::new(), |mut map, (a, b)| {
-            map.entry(b).or_insert(vec![]).push(a);
-            map
-        });
-    let pages = updates
-        .lines()
-        .map(|line| line.split(",").map(|s| s.parse().unwrap()).collect())
-        .collect();
-    (ordering, pages)
+fn parse_input(input: &str) -> Result<(Rules, Vec<Vec<usize>>), ParseError> {
+    let mut lines = parse::lines(Day05::DAY, input);
+
+    let mut ordering = Rules::new();
+    let mut last_line = 0;
+    loop {
+        let Some(line) = lines.next() else {
+            return Err(ParseError::new(
+                Day05::DAY,
+                last_line + 1,
+                "",
+                1,
+                "a blank line between the rules and the updates",
+            ));
+        };
+        if line.text.is_empty() {
+            break;
+        }
+        let (a, b) = line.split_once('|')?;
+        let a = line.parse(a, "a page number")?;
+        let b = line.parse(b, "a page number")?;
+        ordering.entry(b).or_default().push(a);
+        last_line = line.number;
+    }
+
+    let pages = lines
+        .map(|line| {
+            line.text
+                .split(",")
+                .map(|s| line.parse(s, "a page number"))
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((ordering, pages))
 }
 
-fn is_valid(ordering: &HashMap<usize, Vec<usize>>, update: &[usize]) -> bool {
-    update.iter().enumerate().all(|(i, &n)| {
-        ordering
-            .get(&n)
-            .is_none_or(|orders| update[i + 1..].iter().all(|m| !orders.contains(m)))
-    })
+// The page ordering rules as a Graphviz DOT graph, or only the rules between
+// the pages of the 1-based `update`, with broken rules in red. Cyclic rules
+// are drawn as they are, since that is when seeing them helps most.
+fn rules_dot(input: &str, update: Option<usize>) -> Result<String> {
+    let (ordering, updates) = parse_input(input)?;
+    let graph = Graph::from_predecessors(&ordering);
+    let pages = match update {
+        Some(n) => Some(updates.get(n.wrapping_sub(1)).ok_or_else(|| {
+            anyhow!("there is no update {} (the input has {})", n, updates.len())
+        })?),
+        None => None,
+    };
+    Ok(graph.to_dot(pages.map(Vec::as_slice)))
 }
 
-fn sort_invalid_update(ordering: &HashMap<usize, Vec<usize>>, update: Vec<usize>) -> Vec<usize> {
-    update
-        .into_iter()
-        .sorted_by(|a, b| {
-            let orders = ordering.get(&a);
-            match orders {
-                None => Ordering::Equal,
-                Some(orders) => {
-                    if orders.contains(b) {
-                        Ordering::Less
-                    } else {
-                        Ordering::Greater
-                    }
-                }
-            }
-        })
-        .collect()
+// The rules as a whole are cyclic, so only the pages of each update can be
+// required to have a consistent order.
+fn check_acyclic(graph: &Graph<usize>, updates: &[Vec<usize>]) -> Result<()> {
+    for (i, update) in updates.iter().enumerate() {
+        graph.sort(update).with_context(|| {
+            format!(
+                "update {} ({}) cannot be ordered",
+                i + 1,
+                update.iter().join(",")
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn sort_invalid_update(graph: &Graph<usize>, update: &[usize]) -> Vec<usize> {
+    graph
+        .sort(update)
+        .expect("updates are checked to be acyclic when parsing")
+}
+
+// One line per update saying whether it is correctly ordered, followed by
+// an indented line for each rule it breaks and for a cycle among its pages.
+fn explain_updates(graph: &Graph<usize>, updates: &[Vec<usize>]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (i, update) in updates.iter().enumerate() {
+        let violations = graph.violations(update);
+        let status = match violations.len() {
+            0 => "ok".to_string(),
+            1 => "breaks 1 rule".to_string(),
+            n => format!("breaks {} rules", n),
+        };
+        lines.push(format!(
+            "update {} ({}): {}",
+            i + 1,
+            update.iter().join(","),
+            status
+        ));
+        lines.extend(
+            violations
+                .iter()
+                .map(|violation| format!("  {}", violation)),
+        );
+        if let Err(cycle) = graph.sort(update) {
+            lines.push(format!("  cannot be ordered: {}", cycle));
+        }
+    }
+    lines
 }
 
-fn solve_part1(input: &str) -> usize {
-    let (ordering, updates) = parse_input(input);
+fn solve_part1(graph: &Graph<usize>, updates: &[Vec<usize>]) -> usize {
     updates
-        .into_iter()
-        .filter(|update| is_valid(&ordering, &update))
+        .iter()
+        .filter(|update| graph.is_sorted(update))
         .map(|update| update[update.len() / 2])
         .sum()
 }
 
-fn solve_part2(input: &str) -> usize {
-    let (ordering, updates) = parse_input(input);
+fn solve_part2(graph: &Graph<usize>, updates: &[Vec<usize>]) -> usize {
     updates
-        .into_iter()
-        .filter(|update| !is_valid(&ordering, update))
-        .map(|update| sort_invalid_update(&ordering, update))
+        .iter()
+        .filter(|update| !graph.is_sorted(update))
+        .map(|update| sort_invalid_update(graph, update))
         .map(|update| update[update.len() / 2])
         .sum()
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
 
@@ -116,19 +201,111 @@ mod tests {
 
     #[test]
     fn test_is_valid() {
-        let (ordering, _) = parse_input(TEST_INPUT);
-        assert!(is_valid(&ordering, &vec![75, 47, 61, 53, 29]));
+        let (graph, _) = Day05::parse(TEST_INPUT).unwrap();
+        assert!(graph.is_sorted(&vec![75, 47, 61, 53, 29]));
+
+        assert!(!graph.is_sorted(&vec![75, 97, 47, 61, 53]));
+    }
+
+    #[test]
+    fn test_sort_invalid_update() {
+        let (graph, _) = Day05::parse(TEST_INPUT).unwrap();
+        assert_eq!(
+            sort_invalid_update(&graph, &[97, 13, 75, 29, 47]),
+            vec![97, 75, 47, 29, 13]
+        );
+    }
+
+    #[test]
+    fn test_explain_updates() {
+        let (graph, updates) = Day05::parse(TEST_INPUT).unwrap();
+        let lines = explain_updates(&graph, &updates);
+        assert_eq!(lines[0], "update 1 (75,47,61,53,29): ok");
+        assert_eq!(
+            lines[3..6],
+            [
+                "update 4 (75,97,47,61,53): breaks 1 rule",
+                "  97|75: 75 at index 0 comes before 97 at index 1",
+                "update 5 (61,13,29): breaks 1 rule",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_cycle() {
+        let lines = Day05::explain("1|2\n2|3\n3|1\n\n1,2\n3,1,2\n").unwrap();
+        assert_eq!(
+            lines,
+            [
+                "update 1 (1,2): ok",
+                "update 2 (3,1,2): breaks 1 rule",
+                "  2|3: 3 at index 0 comes before 2 at index 2",
+                "  cannot be ordered: ordering rules form a cycle: 1 -> 2 -> 3 -> 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rules_dot() {
+        let dot = rules_dot(TEST_INPUT, Some(4)).unwrap();
+        assert!(dot.contains("\"97\" -> \"75\" [color=red, penwidth=2];"));
+        assert!(dot.contains("\"75\" -> \"47\";"));
+        assert!(!dot.contains("\"13\""));
+        assert_eq!(
+            rules_dot(TEST_INPUT, None).unwrap().matches(" -> ").count(),
+            21
+        );
+        assert!(rules_dot(TEST_INPUT, Some(7)).is_err());
+        assert!(rules_dot(TEST_INPUT, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_repeated_page() {
+        let (graph, updates) = Day05::parse("1|2\n\n1,2,2\n2,1,2\n").unwrap();
+        assert_eq!(sort_invalid_update(&graph, &updates[1]), vec![1, 2, 2]);
+        assert_eq!(
+            (solve_part1(&graph, &updates), solve_part2(&graph, &updates)),
+            (2, 2)
+        );
+    }
+
+    #[test]
+    fn test_rules_dot_cycle() {
+        let dot = rules_dot("1|2\n2|1\n\n1,2\n", Some(1)).unwrap();
+        assert!(dot.contains("\"1\" -> \"2\";"));
+        assert!(dot.contains("\"2\" -> \"1\" [color=red, penwidth=2];"));
+    }
+
+    #[test]
+    fn test_cycle_error() {
+        let error = Day05::parse("1|2\n2|3\n3|1\n\n1,2\n3,1,2\n").unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "update 2 (3,1,2) cannot be ordered: ordering rules form a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("47|53\n97-13\n\n75,47\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+
+        let error = parse_input("47|53\n\n75,,47\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 4));
 
-        assert!(!is_valid(&ordering, &vec![75, 97, 47, 61, 53]));
+        let error = parse_input("47|53\n97|13\n").unwrap_err();
+        assert_eq!(error.line, 3);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(TEST_INPUT), 143);
+        let (graph, updates) = Day05::parse(TEST_INPUT).unwrap();
+        assert_eq!(solve_part1(&graph, &updates), 143);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(TEST_INPUT), 123);
+        let (graph, updates) = Day05::parse(TEST_INPUT).unwrap();
+        assert_eq!(solve_part2(&graph, &updates), 123);
     }
 }
@@ -1,144 +1,523 @@
-use anyhow::Result;
-use std::collections::HashSet;
-use std::fs;
+use std::{collections::HashSet, num::NonZeroUsize, str::FromStr, thread};
 
-use crate::types::{Point, Vector};
+use super::Solution;
+use crate::export::Table;
+use crate::input::normalize;
+use crate::parse::{self, ParseError};
+use crate::types::{Direction, Grid, Point};
+use anyhow::{bail, Result};
+use itertools::Itertools;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day06.txt")?;
+pub struct Day06;
 
-    // Part 1
-    let result1 = solve_part1(&input);
-    println!("Part 1: {}", result1);
+impl Solution for Day06 {
+    const DAY: u8 = 6;
 
-    // Part 2
-    let result2 = solve_part2(&input);
-    println!("Part 2: {}", result2);
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = parse_input(input)?;
+        if find_guards(&map).is_empty() {
+            bail!("the map has no guard (one of `^v<>`)");
+        }
+        Ok(map)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        solve_part2(input)
+    }
 }
 
-fn parse_input(input: &str) -> Map {
-    input
-        .lines()
-        .map(|line| line.chars().map(parse_tile).collect())
-        .collect()
+fn parse_input(input: &str) -> Result<Map, ParseError> {
+    parse::grid(Day06::DAY, input, parse_tile, "one of `.#^v<>`")
 }
 
-type Map = Vec<Vec<Tile>>;
+pub type Map = Grid<Tile>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Tile {
+pub enum Tile {
     Open,
     Obstacle,
-    Guard(Vector),
+    Guard(Direction),
 }
 
-fn parse_tile(c: char) -> Tile {
+fn parse_tile(c: char) -> Option<Tile> {
     match c {
-        '.' => Tile::Open,
-        '#' => Tile::Obstacle,
-        '^' => Tile::Guard(Vector { dx: 0, dy: -1 }),
-        'v' => Tile::Guard(Vector { dx: 0, dy: 1 }),
-        '<' => Tile::Guard(Vector { dx: -1, dy: 0 }),
-        '>' => Tile::Guard(Vector { dx: 1, dy: 0 }),
-        _ => unreachable!(),
+        '.' => Some(Tile::Open),
+        '#' => Some(Tile::Obstacle),
+        _ => Direction::from_arrow(c).map(Tile::Guard),
     }
 }
 
-fn get_tile(map: &Map, pos: &Point) -> Tile {
-    map[pos.y as usize][pos.x as usize].clone()
+/// Where the guard is and which way they are facing.
+pub type GuardPosition = (Point, Direction);
+
+/// How a patrol ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The guard walked off the map.
+    Exit,
+    /// The guard came back to this position, so they will walk in circles.
+    Loop(GuardPosition),
 }
 
-fn turn_right(dir: &Vector) -> Vector {
-    match dir {
-        Vector { dx: 0, dy: -1 } => Vector { dx: 1, dy: 0 },
-        Vector { dx: 1, dy: 0 } => Vector { dx: 0, dy: 1 },
-        Vector { dx: 0, dy: 1 } => Vector { dx: -1, dy: 0 },
-        Vector { dx: -1, dy: 0 } => Vector { dx: 0, dy: -1 },
-        _ => unreachable!(),
-    }
+/// Which way the guard turns when an obstacle is in front of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Turn {
+    #[default]
+    Right,
+    Left,
+    Around,
 }
 
-fn is_in_bounds(map: &Map, pos: &Point) -> bool {
-    pos.x >= 0 && pos.y >= 0 && pos.x < map[0].len() as isize && pos.y < map.len() as isize
+impl Turn {
+    fn apply(self, dir: Direction) -> Direction {
+        match self {
+            Turn::Right => dir.turn_right(),
+            Turn::Left => dir.turn_left(),
+            Turn::Around => dir.opposite(),
+        }
+    }
 }
 
-type GuardPosition = (Point, Vector);
+impl FromStr for Turn {
+    type Err = String;
 
-fn step(
-    map: &Map,
-    pos: &Point,
-    dir: &Vector,
-    visited: &mut HashSet<Point>,
-    positions: &mut HashSet<GuardPosition>,
-) -> Option<usize> {
-    if !positions.insert((pos.clone(), dir.clone())) {
-        return None;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Turn::Right),
+            "left" => Ok(Turn::Left),
+            "around" => Ok(Turn::Around),
+            _ => Err(format!(
+                "unknown turn `{}` (expected `right`, `left` or `around`)",
+                s
+            )),
+        }
     }
+}
+
+/// What happens when the guard walks off an edge of the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Edges {
+    /// The patrol ends.
+    #[default]
+    Exit,
+    /// The guard comes back in on the opposite edge, as on a torus.
+    Wrap,
+}
 
-    let next = pos + dir;
-    if !is_in_bounds(map, &next) {
-        return Some(0);
+/// How guards patrol. The default is the puzzle's: turn right and leave at
+/// the edge. The solutions always use the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rules {
+    pub turn: Turn,
+    pub edges: Edges,
+}
+
+/// The guard's walk as an iterator over their positions, starting with the
+/// initial one. A turn on the spot is a step of its own. The iterator ends
+/// when the guard leaves the map or is about to repeat a position, after
+/// which `outcome` says which of the two happened.
+pub struct Patrol<'a> {
+    map: &'a Map,
+    rules: Rules,
+    next: Option<GuardPosition>,
+    seen: HashSet<GuardPosition>,
+    outcome: Option<Outcome>,
+}
+
+impl<'a> Patrol<'a> {
+    pub fn new(map: &'a Map, start: GuardPosition) -> Self {
+        Self::with_rules(map, start, Rules::default())
     }
 
-    match get_tile(map, &next) {
-        Tile::Obstacle => step(map, pos, &turn_right(dir), visited, positions),
-        _ => {
-            let ds = if visited.insert(next.clone()) { 1 } else { 0 };
-            step(map, &next, dir, visited, positions).map(|ds2| ds + ds2)
+    pub fn with_rules(map: &'a Map, start: GuardPosition, rules: Rules) -> Self {
+        Self {
+            map,
+            rules,
+            next: Some(start),
+            seen: HashSet::new(),
+            outcome: None,
         }
     }
+
+    /// How the patrol ended, or `None` while it is still going.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    /// Walks the rest of the patrol and returns how it ended.
+    pub fn run(mut self) -> Outcome {
+        self.by_ref().for_each(drop);
+        self.outcome.unwrap()
+    }
 }
 
-fn go(map: &Map, pos: &Point, dir: &Vector) -> Option<(usize, HashSet<Point>)> {
-    let mut visited = HashSet::from([pos.clone()]);
-    let mut positions = HashSet::new();
-    step(map, pos, dir, &mut visited, &mut positions).map(|ds| (ds + 1, visited))
+impl Iterator for Patrol<'_> {
+    type Item = GuardPosition;
+
+    fn next(&mut self) -> Option<GuardPosition> {
+        let (pos, dir) = self.next.take()?;
+        if !self.seen.insert((pos, dir)) {
+            self.outcome = Some(Outcome::Loop((pos, dir)));
+            return None;
+        }
+
+        let mut ahead = pos + dir.to_vector();
+        if self.rules.edges == Edges::Wrap {
+            let (width, height) = (self.map.width() as isize, self.map.height() as isize);
+            ahead = Point::new(ahead.x.rem_euclid(width), ahead.y.rem_euclid(height));
+        }
+        self.next = match self.map.get(&ahead) {
+            None => {
+                self.outcome = Some(Outcome::Exit);
+                None
+            }
+            Some(Tile::Obstacle) => Some((pos, self.rules.turn.apply(dir))),
+            Some(_) => Some((ahead, dir)),
+        };
+        Some((pos, dir))
+    }
 }
 
-fn find_start(map: &Map) -> (Point, Vector) {
-    let initial_guard_dir = Vector { dx: 0, dy: -1 };
-    let initial_guard_tile = Tile::Guard(initial_guard_dir.clone());
-    let initial_pos = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, row)| {
-            row.iter()
-                .position(|c| c == &initial_guard_tile)
-                .map(|x| Point::new((x, y)))
+/// Every guard on the map, in reading order, facing the way their arrow points.
+pub fn find_guards(map: &Map) -> Vec<GuardPosition> {
+    map.iter()
+        .filter_map(|(pos, tile)| match tile {
+            Tile::Guard(dir) => Some((pos, *dir)),
+            _ => None,
         })
-        .unwrap();
-    (initial_pos, initial_guard_dir)
+        .collect()
+}
+
+// Index of a guard position in flat per-cell, per-heading tables.
+fn state_index(map: &Map, (pos, dir): GuardPosition) -> usize {
+    (pos.y as usize * map.width() + pos.x as usize) * 4 + dir as usize
+}
+
+// Fixed-size set of small integers, one bit each.
+struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    // Adds `i`, returning whether it was not already present.
+    fn insert(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        let absent = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        absent
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+    }
 }
 
-fn start_simulation(map: &Map) -> usize {
-    let (start, dir) = find_start(map);
-    go(&map, &start, &dir).unwrap().0
+// For every guard position, where the guard stops when walking straight
+// ahead: the last point before the next obstacle, or `None` if they leave the
+// map first. This lets a patrol jump from turn to turn.
+struct JumpTable<'a> {
+    map: &'a Map,
+    stops: Vec<Option<Point>>,
 }
 
-fn add_obstacle(map: &Map, pos: &Point) -> Map {
-    let mut new_map = map.clone();
-    new_map[pos.y as usize][pos.x as usize] = Tile::Obstacle;
-    new_map
+impl<'a> JumpTable<'a> {
+    fn new(map: &'a Map) -> Self {
+        let mut stops = vec![None; map.width() * map.height() * 4];
+        let points = map.points().collect::<Vec<_>>();
+        for dir in Direction::iter() {
+            // Fill in each point after the point ahead of it.
+            let ordered: Box<dyn Iterator<Item = &Point>> = match dir {
+                Direction::North | Direction::West => Box::new(points.iter()),
+                Direction::South | Direction::East => Box::new(points.iter().rev()),
+            };
+            for &pos in ordered {
+                let ahead = pos + dir.to_vector();
+                stops[state_index(map, (pos, dir))] = match map.get(&ahead) {
+                    None => None,
+                    Some(Tile::Obstacle) => Some(pos),
+                    Some(_) => stops[state_index(map, (ahead, dir))],
+                };
+            }
+        }
+        Self { map, stops }
+    }
+
+    // Whether the guard, starting at `start` with one more obstacle placed
+    // at `obstacle`, ends up in a loop. `seen` must be empty.
+    fn loops(&self, obstacle: Point, start: GuardPosition, seen: &mut BitSet) -> bool {
+        let (mut pos, mut dir) = start;
+        loop {
+            let step = dir.to_vector();
+            let stop = self.stops[state_index(self.map, (pos, dir))];
+            let distance = pos.manhattan(obstacle);
+            let blocked = obstacle - pos == step * distance
+                && stop.is_none_or(|stop| distance <= pos.manhattan(stop));
+            pos = match (blocked, stop) {
+                (true, _) => obstacle - step,
+                (false, Some(stop)) => stop,
+                (false, None) => return false,
+            };
+            dir = dir.turn_right();
+            if !seen.insert(state_index(self.map, (pos, dir))) {
+                return true;
+            }
+        }
+    }
 }
 
-fn solve_part1(input: &str) -> usize {
-    let map = parse_input(input);
-    start_simulation(&map)
+// Each point the guard reaches, other than the start, with the guard's
+// position just before first reaching it. An obstacle placed there changes
+// nothing before that moment, so checking it can start from that position.
+fn candidates(map: &Map, start: GuardPosition) -> Vec<(Point, GuardPosition)> {
+    let mut reached = HashSet::from([start.0]);
+    Patrol::new(map, start)
+        .tuple_windows()
+        .filter(|&(_, (pos, _))| reached.insert(pos))
+        .map(|(before, (pos, _))| (pos, before))
+        .collect()
 }
 
-fn solve_part2(input: &str) -> usize {
-    let map = parse_input(input);
-    let (start, dir) = find_start(&map);
-    let (_, visited) = go(&map, &start, &dir).unwrap();
-    visited
+const RESET: &str = "\x1b[0m";
+const VISITED: &str = "\x1b[2m";
+const GUARD: &str = "\x1b[1;32m";
+const ADDED: &str = "\x1b[1;33m";
+const REPEATED: &str = "\x1b[1;97;41m";
+
+// The map as colored text: the added obstacle as `O`, visited points as `X`
+// and the guards as arrows, on a red background if `repeated`.
+fn render(
+    map: &Map,
+    obstacle: Option<Point>,
+    visited: &HashSet<Point>,
+    guards: &[GuardPosition],
+    repeated: bool,
+) -> String {
+    let mut frame = String::new();
+    for (point, tile) in map.iter() {
+        if let Some((_, dir)) = guards.iter().find(|(pos, _)| *pos == point) {
+            let color = if repeated { REPEATED } else { GUARD };
+            frame.push_str(&format!("{}{}{}", color, dir.arrow(), RESET));
+        } else {
+            match tile {
+                _ if Some(point) == obstacle => frame.push_str(&format!("{}O{}", ADDED, RESET)),
+                Tile::Obstacle => frame.push('#'),
+                _ if visited.contains(&point) => frame.push_str(&format!("{}X{}", VISITED, RESET)),
+                _ => frame.push('.'),
+            }
+        }
+        if point.x as usize == map.width() - 1 {
+            frame.push('\n');
+        }
+    }
+    frame
+}
+
+// Describes where each guard is, e.g. "guard at (1, 2) facing North".
+fn describe(guards: &[GuardPosition]) -> String {
+    guards
+        .iter()
+        .map(|(pos, dir)| format!("guard at {} facing {:?}", pos, dir))
+        .join(", ")
+}
+
+/// Plays the patrol of every guard on `input` under `rules`, with an extra
+/// obstacle at `obstacle` if given, passing `show` one frame per step in ANSI
+/// colors. Guards move together and do not block each other. If any guard
+/// loops, the last frame highlights the positions they came back to.
+pub fn animate(
+    input: &str,
+    obstacle: Option<Point>,
+    rules: Rules,
+    mut show: impl FnMut(&str) -> Result<()>,
+) -> Result<Vec<Outcome>> {
+    let mut map = Day06::parse(&normalize(input))?;
+    let guards = find_guards(&map);
+    if let Some(obstacle) = obstacle {
+        match map.get(&obstacle) {
+            Some(Tile::Open) => map[&obstacle] = Tile::Obstacle,
+            Some(_) => bail!(
+                "cannot place an obstacle at {}, which is not open",
+                obstacle
+            ),
+            None => bail!(
+                "cannot place an obstacle at {}, which is outside the map",
+                obstacle
+            ),
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut patrols = guards
+        .into_iter()
+        .map(|guard| Patrol::with_rules(&map, guard, rules))
+        .collect::<Vec<_>>();
+    for step in 0.. {
+        let guards = patrols
+            .iter_mut()
+            .filter_map(Iterator::next)
+            .collect::<Vec<_>>();
+        if guards.is_empty() {
+            break;
+        }
+        visited.extend(guards.iter().map(|&(pos, _)| pos));
+        show(&format!(
+            "Step {}: {}\n{}",
+            step,
+            describe(&guards),
+            render(&map, obstacle, &visited, &guards, false)
+        ))?;
+    }
+
+    let outcomes = patrols
         .iter()
-        .filter(|p| {
-            let map = add_obstacle(&map, p);
-            go(&map, &start, &dir).is_none()
+        .map(|patrol| patrol.outcome().unwrap())
+        .collect::<Vec<_>>();
+    let repeated = outcomes
+        .iter()
+        .filter_map(|outcome| match outcome {
+            Outcome::Loop(guard) => Some(*guard),
+            Outcome::Exit => None,
         })
-        .count()
+        .collect::<Vec<_>>();
+    if !repeated.is_empty() {
+        show(&format!(
+            "Loop: {} again\n{}",
+            describe(&repeated),
+            render(&map, obstacle, &visited, &repeated, true)
+        ))?;
+    }
+    Ok(outcomes)
+}
+
+fn solve_part1(map: &Map) -> usize {
+    visited_by_all(map, Rules::default()).len()
+}
+
+/// The distinct points walked over by any of the map's guards under `rules`.
+pub fn visited_by_all(map: &Map, rules: Rules) -> HashSet<Point> {
+    find_guards(map)
+        .into_iter()
+        .flat_map(|guard| Patrol::with_rules(map, guard, rules))
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// Every point where one more obstacle makes a guard loop, in reading order.
+/// With several guards, it is enough for one of them to loop. Only the
+/// default rules are supported.
+pub fn loop_obstacles(map: &Map) -> Vec<Point> {
+    let guards = find_guards(map);
+    let starts = guards.iter().map(|&(pos, _)| pos).collect::<HashSet<_>>();
+    let candidates = guards
+        .into_iter()
+        .flat_map(|guard| candidates(map, guard))
+        .filter(|(obstacle, _)| !starts.contains(obstacle))
+        .collect::<Vec<_>>();
+    let table = JumpTable::new(map);
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_size = candidates.len().div_ceil(threads).max(1);
+
+    let mut obstacles = thread::scope(|scope| {
+        let workers = candidates
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let table = &table;
+                scope.spawn(move || {
+                    let mut seen = BitSet::new(table.stops.len());
+                    chunk
+                        .iter()
+                        .filter(|&&(obstacle, before)| {
+                            seen.clear();
+                            table.loops(obstacle, before, &mut seen)
+                        })
+                        .map(|&(obstacle, _)| obstacle)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+    obstacles.sort();
+    obstacles.dedup();
+    obstacles
+}
+
+fn solve_part2(map: &Map) -> usize {
+    loop_obstacles(map).len()
+}
+
+/// What `export` writes out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatrolData {
+    /// Every guard position of the patrol in order, turns included.
+    Path,
+    /// The points where one more obstacle makes a guard loop.
+    Obstacles,
+}
+
+impl FromStr for PatrolData {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "path" => Ok(PatrolData::Path),
+            "obstacles" => Ok(PatrolData::Obstacles),
+            _ => Err(format!(
+                "unknown data `{}` (expected `path` or `obstacles`)",
+                s
+            )),
+        }
+    }
+}
+
+/// The patrol paths under `rules` or the loop-causing obstacles for `input`
+/// as a table with `x` and `y` columns, plus for the paths the guard's number
+/// in reading order from 1 and their `heading`.
+pub fn export(input: &str, data: PatrolData, rules: Rules) -> Result<Table> {
+    let map = Day06::parse(&normalize(input))?;
+    Ok(match data {
+        PatrolData::Path => {
+            let mut table = Table::new(&["guard", "x", "y", "heading"]);
+            for (guard, start) in (1..).zip(find_guards(&map)) {
+                for (pos, dir) in Patrol::with_rules(&map, start, rules) {
+                    table.push(vec![
+                        guard.into(),
+                        pos.x.into(),
+                        pos.y.into(),
+                        dir.name().into(),
+                    ]);
+                }
+            }
+            table
+        }
+        PatrolData::Obstacles if rules != Rules::default() => {
+            bail!("loop-causing obstacles are only found for the default rules")
+        }
+        PatrolData::Obstacles => {
+            let mut table = Table::new(&["x", "y"]);
+            for pos in loop_obstacles(&map) {
+                table.push(vec![pos.x.into(), pos.y.into()]);
+            }
+            table
+        }
+    })
 }
 
 #[cfg(test)]
@@ -159,23 +538,283 @@ mod tests {
 ";
 
     #[test]
-    fn test_go() {
-        let map: Map = vec![vec![Tile::Guard(Vector { dx: 0, dy: -1 })]];
+    fn test_patrol_exit() {
+        let map: Map = Grid::new(1, 1, vec![Tile::Guard(Direction::North)]);
+        let mut patrol = Patrol::new(&map, (Point::new(0, 0), Direction::North));
+        assert_eq!(patrol.next(), Some((Point::new(0, 0), Direction::North)));
+        assert_eq!(patrol.outcome(), Some(Outcome::Exit));
+        assert_eq!(patrol.next(), None);
+    }
+
+    #[test]
+    fn test_patrol_turns_and_loops() {
+        let map = parse_input("#..\n...\n^..\n").unwrap();
+        let mut patrol = Patrol::new(&map, find_start(&map));
+        assert_eq!(
+            patrol.by_ref().take(2).collect::<Vec<_>>(),
+            vec![
+                (Point::new(0, 2), Direction::North),
+                (Point::new(0, 1), Direction::North),
+            ]
+        );
+        assert_eq!(patrol.outcome(), None);
+        assert_eq!(patrol.next(), Some((Point::new(0, 1), Direction::East)));
+        assert_eq!(patrol.run(), Outcome::Exit);
+
+        let map = parse_input(".#..\n...#\n#...\n..#.\n").unwrap();
+        let start = (Point::new(1, 1), Direction::North);
+        assert_eq!(Patrol::new(&map, start).run(), Outcome::Loop(start));
+    }
+
+    // The first guard in reading order.
+    fn find_start(map: &Map) -> GuardPosition {
+        find_guards(map)[0]
+    }
+
+    // Tries every obstacle position on a fresh copy of the map.
+    fn brute_force_part2(map: &Map) -> usize {
+        let guards = find_guards(map);
+        visited_by_all(map, Rules::default())
+            .into_iter()
+            .filter(|&p| guards.iter().all(|&(start, _)| p != start))
+            .filter(|&p| {
+                let mut map = map.clone();
+                map[&p] = Tile::Obstacle;
+                guards
+                    .iter()
+                    .any(|&guard| matches!(Patrol::new(&map, guard).run(), Outcome::Loop(_)))
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_part2_matches_brute_force() {
+        let maps = [
+            TEST_INPUT,
+            "#...\n...#\n.^..\n..#.\n",
+            ".#..\n....\n#^.#\n..#.\n",
+            "..#..\n....#\n.....\n#.^..\n...#.\n",
+            "^\n",
+            // Several guards, some walking over each other's starts.
+            "..#.......\n.....#..#.\n#.......<.\n.......#..\n.^........\n....#.....\n.......>..\n#.....#...\n",
+            ">..#\n#...\n...^\n.#..\n",
+            ".#....>\n.^.#...\n.......\n..#....\n",
+        ];
+        for input in maps {
+            let map = parse_input(input).unwrap();
+            assert_eq!(solve_part2(&map), brute_force_part2(&map), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_jump_table() {
+        let map = parse_input(TEST_INPUT).unwrap();
+        let table = JumpTable::new(&map);
+        let stop = |pos, dir| table.stops[state_index(&map, (pos, dir))];
+        assert_eq!(
+            stop(Point::new(4, 6), Direction::North),
+            Some(Point::new(4, 1))
+        );
+        assert_eq!(
+            stop(Point::new(4, 1), Direction::East),
+            Some(Point::new(8, 1))
+        );
+        assert_eq!(
+            stop(Point::new(4, 6), Direction::West),
+            Some(Point::new(2, 6))
+        );
+        assert_eq!(stop(Point::new(0, 0), Direction::West), None);
+        assert_eq!(stop(Point::new(0, 9), Direction::South), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let map = parse_input("#..\n...\n^..\n").unwrap();
+        let visited = HashSet::from([Point::new(0, 2), Point::new(0, 1)]);
+        let guard = (Point::new(0, 1), Direction::East);
         assert_eq!(
-            go(&map, &Point::new((0, 0)), &Vector { dx: 0, dy: -1 })
-                .unwrap()
-                .0,
-            1
+            render(&map, Some(Point::new(2, 2)), &visited, &[guard], false),
+            format!("#..\n{GUARD}>{RESET}..\n{VISITED}X{RESET}.{ADDED}O{RESET}\n")
         );
+        assert!(render(&map, None, &visited, &[guard], true).contains(REPEATED));
+    }
+
+    #[test]
+    fn test_animate() {
+        let mut frames = Vec::new();
+        let outcomes = animate("#..\n...\n^..\n", None, Rules::default(), |frame| {
+            frames.push(frame.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(outcomes, vec![Outcome::Exit]);
+        assert_eq!(frames.len(), 5);
+        assert!(frames[2].starts_with("Step 2: guard at (0, 1) facing East\n"));
+
+        let mut last = String::new();
+        let outcomes = animate(
+            TEST_INPUT,
+            Some(Point::new(3, 6)),
+            Rules::default(),
+            |frame| {
+                last = frame.to_string();
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert!(matches!(outcomes[..], [Outcome::Loop(_)]));
+        assert!(last.starts_with("Loop: guard at"));
+        assert!(last.contains(REPEATED));
+
+        let rules = Rules::default();
+        assert!(animate(TEST_INPUT, Some(Point::new(4, 0)), rules, |_| Ok(())).is_err());
+        assert!(animate(TEST_INPUT, Some(Point::new(4, 6)), rules, |_| Ok(())).is_err());
+        assert!(animate(TEST_INPUT, Some(Point::new(10, 0)), rules, |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_animate_several_guards() {
+        let mut frames = Vec::new();
+        let outcomes = animate(">..\n...\n..<\n", None, Rules::default(), |frame| {
+            frames.push(frame.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(outcomes, vec![Outcome::Exit, Outcome::Exit]);
+        assert_eq!(frames.len(), 3);
+        assert!(frames[0]
+            .starts_with("Step 0: guard at (0, 0) facing East, guard at (2, 2) facing West\n"));
+    }
+
+    #[test]
+    fn test_start_heading_from_map() {
+        let map = Day06::parse("...\n.>#\n...\n").unwrap();
+        assert_eq!(find_start(&map), (Point::new(1, 1), Direction::East));
+        assert_eq!(
+            Patrol::new(&map, find_start(&map)).nth(1),
+            Some((Point::new(1, 1), Direction::South))
+        );
+        assert!(Day06::parse("...\n.#.\n").is_err());
+    }
+
+    #[test]
+    fn test_turn_rules() {
+        let map = parse_input("...\n.^.\n...\n").unwrap();
+        let map = {
+            let mut map = map;
+            map[&Point::new(1, 0)] = Tile::Obstacle;
+            map
+        };
+        let start = find_start(&map);
+        let second = |turn| {
+            let rules = Rules {
+                turn,
+                ..Rules::default()
+            };
+            Patrol::with_rules(&map, start, rules).nth(1).unwrap().1
+        };
+        assert_eq!(second(Turn::Right), Direction::East);
+        assert_eq!(second(Turn::Left), Direction::West);
+        assert_eq!(second(Turn::Around), Direction::South);
+        assert_eq!("around".parse(), Ok(Turn::Around));
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let map = Day06::parse("...\n.^.\n...\n").unwrap();
+        let rules = Rules {
+            edges: Edges::Wrap,
+            ..Rules::default()
+        };
+        let start = find_start(&map);
+        let mut patrol = Patrol::with_rules(&map, start, rules);
+        assert_eq!(
+            patrol.by_ref().map(|(pos, _)| pos).collect::<Vec<_>>(),
+            vec![Point::new(1, 1), Point::new(1, 0), Point::new(1, 2)]
+        );
+        assert_eq!(patrol.outcome(), Some(Outcome::Loop(start)));
+    }
+
+    #[test]
+    fn test_visited_by_all() {
+        let map = Day06::parse(">..\n...\n..^\n").unwrap();
+        assert_eq!(visited_by_all(&map, Rules::default()).len(), 5);
+        assert_eq!(solve_part1(&map), 5);
+    }
+
+    #[test]
+    fn test_loop_obstacles() {
+        let map = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            loop_obstacles(&map),
+            vec![
+                Point::new(3, 6),
+                Point::new(6, 7),
+                Point::new(3, 8),
+                Point::new(1, 8),
+                Point::new(7, 7),
+                Point::new(7, 9),
+            ]
+            .into_iter()
+            .sorted()
+            .collect::<Vec<_>>()
+        );
+
+        // Only the second guard in reading order can be made to loop.
+        let map = parse_input(".#....>\n.^.#...\n.......\n..#....\n").unwrap();
+        assert_eq!(loop_obstacles(&map), vec![Point::new(0, 2)]);
+    }
+
+    #[test]
+    fn test_export() {
+        use crate::export::Format;
+
+        let path = export("#..\n...\n^..\n", PatrolData::Path, Rules::default()).unwrap();
+        assert_eq!(
+            path.write(Format::Csv),
+            "guard,x,y,heading\n1,0,2,north\n1,0,1,north\n1,0,1,east\n1,1,1,east\n1,2,1,east\n"
+        );
+
+        let rules = Rules {
+            turn: Turn::Left,
+            edges: Edges::Exit,
+        };
+        let path = export("#..\n..<\n^..\n", PatrolData::Path, rules).unwrap();
+        assert_eq!(
+            path.write(Format::Csv).lines().skip(3).collect::<Vec<_>>(),
+            vec!["1,0,1,west", "2,0,2,north", "2,0,1,north", "2,0,1,west"]
+        );
+        assert!(export(TEST_INPUT, PatrolData::Obstacles, rules).is_err());
+
+        let obstacles = export(TEST_INPUT, PatrolData::Obstacles, Rules::default()).unwrap();
+        assert_eq!(obstacles.len(), 6);
+        assert!(obstacles
+            .write(Format::Json)
+            .starts_with("[\n  {\"x\": 3, \"y\": 6},\n"));
+        assert_eq!("path".parse(), Ok(PatrolData::Path));
+    }
+
+    #[test]
+    fn test_long_corridor() {
+        let mut map: Map = Grid::filled(1, 100_000, Tile::Open);
+        map[&Point::new(0, 99_999)] = Tile::Guard(Direction::North);
+        assert_eq!(solve_part1(&map), 100_000);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("..#\n.^x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "one of `.#^v<>`");
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(TEST_INPUT), 41);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 41);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(TEST_INPUT), 6);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT).unwrap()), 6);
     }
 }
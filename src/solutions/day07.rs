@@ -1,86 +1,300 @@
+use std::{fmt, str::FromStr};
+
+use super::Solution;
+use crate::input::normalize;
+use crate::parse::{self, ParseError};
 use anyhow::Result;
-use itertools::{repeat_n, Itertools};
-use std::fs;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day07.txt")?;
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<(u64, Vec<u64>)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    // Part 1
-    let result1 = solve_part1(&input);
-    println!("Part 1: {}", result1);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-    // Part 2
-    let result2 = solve_part2(&input);
-    println!("Part 2: {}", result2);
+    fn part1(input: &Self::Input) -> u64 {
+        solve_part1(input)
+    }
 
-    Ok(())
+    fn part2(input: &Self::Input) -> u64 {
+        solve_part2(input)
+    }
 }
 
-fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
-    input
-        .lines()
+fn parse_input(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    parse::lines(Day07::DAY, input)
         .map(|line| {
-            let (left, right) = line.split_once(':').unwrap();
-            (
-                left.parse().unwrap(),
-                right
-                    .split_whitespace()
-                    .map(|s| s.parse().unwrap())
-                    .collect(),
-            )
+            let (left, right) = line.split_once(':')?;
+            let numbers = right
+                .split_whitespace()
+                .map(|s| line.parse(s, "a number"))
+                .collect::<Result<Vec<_>, _>>()?;
+            if numbers.is_empty() {
+                return Err(line.error_at_end("at least one number"));
+            }
+            Ok((line.parse(left, "a test value")?, numbers))
         })
         .collect()
 }
 
-const POSSIBLE_OPERATORS: &[char] = &['+', '*'];
-const POSSIBLE_OPERATORS_2: &[char] = &['+', '*', '|'];
+/// A binary operator combining the value so far with the next number, left
+/// to right. Implement it to try equations with operators of your own.
+pub trait Operator {
+    /// `acc` combined with `n`, or `None` if that is undefined or does not
+    /// fit in a `u64`.
+    fn apply(&self, acc: u64, n: u64) -> Option<u64>;
+
+    /// The values `acc` for which `apply(acc, n)` is `result`. Undoing an
+    /// operator lets the solver prune impossible branches early; the default
+    /// gives up and has every value before it tried forwards instead.
+    fn inverse(&self, _result: u64, _n: u64) -> Inverse {
+        Inverse::Ambiguous
+    }
+}
+
+impl<O: Operator + ?Sized> Operator for &O {
+    fn apply(&self, acc: u64, n: u64) -> Option<u64> {
+        (**self).apply(acc, n)
+    }
+
+    fn inverse(&self, result: u64, n: u64) -> Inverse {
+        (**self).inverse(result, n)
+    }
+}
 
-fn test_combination(expected: u64, numbers: &[u64], operators: &[&char]) -> bool {
-    let mut operands = numbers.iter().rev().collect::<Vec<_>>();
-    let total = operators
-        .iter()
-        .fold(operands.pop().unwrap().to_owned(), |acc, op| match op {
-            '+' => acc + operands.pop().unwrap(),
-            '*' => acc * operands.pop().unwrap(),
-            '|' => (acc.to_string() + operands.pop().unwrap().to_string().as_str())
-                .parse()
-                .unwrap(),
-            _ => acc,
-        });
+/// The operators selectable with `calibrate --operators`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinOperator {
+    Add,
+    Mul,
+    /// Writes the digits of the number after those of the value so far, both
+    /// in the given base. Bases below 2 give no value.
+    Concat(u64),
+    Sub,
+    /// Integer division, rounding down.
+    Div,
+    Pow,
+    Xor,
+}
 
-    total == expected
+/// What undoing an operator says about the value it was applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inverse {
+    /// No value gives the result.
+    Impossible,
+    /// Only this value gives the result.
+    Unique(u64),
+    /// Possibly several values do, which have to be found by applying the
+    /// operator forwards.
+    Ambiguous,
 }
 
-fn find_combination(total: u64, numbers: &[u64], operators: &[char]) -> bool {
-    let operators = repeat_n(operators, numbers.len() - 1)
-        .multi_cartesian_product()
-        .collect::<Vec<_>>();
-    operators
-        .iter()
-        .any(|ops| test_combination(total, numbers, ops))
+pub const PART1_OPERATORS: &[BuiltinOperator] = &[BuiltinOperator::Add, BuiltinOperator::Mul];
+pub const PART2_OPERATORS: &[BuiltinOperator] = &[
+    BuiltinOperator::Add,
+    BuiltinOperator::Mul,
+    BuiltinOperator::Concat(10),
+];
+
+impl Operator for BuiltinOperator {
+    /// Undefined for e.g. a negative difference or a division by zero.
+    fn apply(&self, acc: u64, n: u64) -> Option<u64> {
+        match *self {
+            BuiltinOperator::Add => acc.checked_add(n),
+            BuiltinOperator::Mul => acc.checked_mul(n),
+            BuiltinOperator::Concat(base) => {
+                acc.checked_mul(concat_shift(n, base)?)?.checked_add(n)
+            }
+            BuiltinOperator::Sub => acc.checked_sub(n),
+            BuiltinOperator::Div => acc.checked_div(n),
+            BuiltinOperator::Pow => acc.checked_pow(exponent(n)),
+            BuiltinOperator::Xor => Some(acc ^ n),
+        }
+    }
+
+    fn inverse(&self, result: u64, n: u64) -> Inverse {
+        let unique = |acc: Option<u64>| acc.map_or(Inverse::Impossible, Inverse::Unique);
+        match *self {
+            BuiltinOperator::Add => unique(result.checked_sub(n)),
+            // Anything times zero is zero.
+            BuiltinOperator::Mul if n == 0 => ambiguous_if(result == 0),
+            BuiltinOperator::Mul => unique(result.is_multiple_of(n).then(|| result / n)),
+            BuiltinOperator::Concat(base) => unique(
+                concat_shift(n, base)
+                    .and_then(|shift| (result % shift == n).then(|| result / shift)),
+            ),
+            BuiltinOperator::Sub => unique(result.checked_add(n)),
+            BuiltinOperator::Div => ambiguous_if(n != 0),
+            // Anything to the power of zero is one.
+            BuiltinOperator::Pow if n == 0 => ambiguous_if(result == 1),
+            BuiltinOperator::Pow => unique(exact_root(result, n)),
+            BuiltinOperator::Xor => Inverse::Unique(result ^ n),
+        }
+    }
 }
 
-fn solve_part1(input: &str) -> u64 {
-    let data = parse_input(input);
-    data.iter()
-        .filter_map(|(total, numbers)| {
-            find_combination(*total, &numbers, POSSIBLE_OPERATORS).then_some(total)
-        })
-        .sum()
+impl FromStr for BuiltinOperator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let concat = s.strip_prefix("concat").or_else(|| s.strip_prefix("||"));
+        match s {
+            "add" | "+" => Ok(BuiltinOperator::Add),
+            "mul" | "*" => Ok(BuiltinOperator::Mul),
+            "sub" | "-" => Ok(BuiltinOperator::Sub),
+            "div" | "/" => Ok(BuiltinOperator::Div),
+            "pow" | "^" => Ok(BuiltinOperator::Pow),
+            "xor" => Ok(BuiltinOperator::Xor),
+            _ => match concat {
+                Some("") => Ok(BuiltinOperator::Concat(10)),
+                Some(base) => match base.parse() {
+                    Ok(base) if base >= 2 => Ok(BuiltinOperator::Concat(base)),
+                    _ => Err(format!("invalid concatenation base `{}`", base)),
+                },
+                None => Err(format!(
+                    "unknown operator `{}` (expected `add`, `mul`, `concat`, `concat<base>`, \
+                     `sub`, `div`, `pow` or `xor`)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for BuiltinOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuiltinOperator::Add => f.pad("add"),
+            BuiltinOperator::Mul => f.pad("mul"),
+            BuiltinOperator::Concat(10) => f.pad("concat"),
+            BuiltinOperator::Concat(base) => f.pad(&format!("concat{}", base)),
+            BuiltinOperator::Sub => f.pad("sub"),
+            BuiltinOperator::Div => f.pad("div"),
+            BuiltinOperator::Pow => f.pad("pow"),
+            BuiltinOperator::Xor => f.pad("xor"),
+        }
+    }
+}
+
+// For operators whose undo has several answers when it has any.
+fn ambiguous_if(possible: bool) -> Inverse {
+    if possible {
+        Inverse::Ambiguous
+    } else {
+        Inverse::Impossible
+    }
+}
+
+// The power of `base` that concatenating `n` to another number multiplies it
+// by, if it fits. Bases below 2 have no digits, so nothing can be
+// concatenated in them.
+fn concat_shift(n: u64, base: u64) -> Option<u64> {
+    if base < 2 {
+        return None;
+    }
+    base.checked_pow(n.checked_ilog(base).unwrap_or(0) + 1)
+}
+
+// Exponents past `u32::MAX` only leave 0 and 1 without overflowing, just as
+// `u32::MAX` itself does.
+fn exponent(n: u64) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+// The `u64` whose `n`th power is exactly `value`, if any. A floating point
+// estimate is corrected to the integer root `r` with `r^n <= value <
+// (r + 1)^n`, which for `n >= 2` is at most `2^32`.
+fn exact_root(value: u64, n: u64) -> Option<u64> {
+    if n == 1 {
+        return Some(value);
+    }
+    let n = exponent(n);
+    let exceeds = |root: u64| root.checked_pow(n).is_none_or(|power| power > value);
+    let mut root = (value as f64).powf(1.0 / n as f64) as u64;
+    while root > 0 && exceeds(root) {
+        root -= 1;
+    }
+    while !exceeds(root + 1) {
+        root += 1;
+    }
+    (root.pow(n) == value).then_some(root)
+}
+
+// Whether `numbers`, combined left to right with `operators`, can produce
+// `expected`. Works backwards from the last number, undoing each operator and
+// pruning a branch as soon as that is impossible, e.g. when a subtraction
+// would go negative, a division is not exact or the digits do not match.
+// Operators that cannot be undone uniquely fall back to trying every value
+// of the numbers before them forwards.
+fn find_combination<O: Operator>(expected: u64, numbers: &[u64], operators: &[O]) -> bool {
+    let Some((&last, rest)) = numbers.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return expected == last;
+    }
+
+    operators.iter().any(|op| match op.inverse(expected, last) {
+        Inverse::Impossible => false,
+        Inverse::Unique(acc) => find_combination(acc, rest, operators),
+        Inverse::Ambiguous => any_value(rest[0], &rest[1..], operators, &|acc| {
+            op.apply(acc, last) == Some(expected)
+        }),
+    })
+}
+
+// Whether any way of combining `acc` with `rest` left to right gives a value
+// accepted by `accept`.
+fn any_value<O: Operator>(
+    acc: u64,
+    rest: &[u64],
+    operators: &[O],
+    accept: &dyn Fn(u64) -> bool,
+) -> bool {
+    let Some((&n, rest)) = rest.split_first() else {
+        return accept(acc);
+    };
+    operators.iter().any(|op| {
+        op.apply(acc, n)
+            .is_some_and(|acc| any_value(acc, rest, operators, accept))
+    })
 }
 
-fn solve_part2(input: &str) -> u64 {
-    let data = parse_input(input);
+fn total_calibration<O: Operator>(data: &[(u64, Vec<u64>)], operators: &[O]) -> u64 {
     data.iter()
         .filter_map(|(total, numbers)| {
-            find_combination(*total, &numbers, POSSIBLE_OPERATORS_2).then_some(total)
+            find_combination(*total, numbers, operators).then_some(total)
         })
         .sum()
 }
 
+fn solve_part1(data: &[(u64, Vec<u64>)]) -> u64 {
+    total_calibration(data, PART1_OPERATORS)
+}
+
+fn solve_part2(data: &[(u64, Vec<u64>)]) -> u64 {
+    total_calibration(data, PART2_OPERATORS)
+}
+
+/// The total calibration result when the equations may use `operators`. To
+/// mix built-in operators with others, pass them as `&dyn Operator`.
+pub fn calibrate<O: Operator>(input: &str, operators: &[O]) -> Result<u64> {
+    Ok(total_calibration(
+        &Day07::parse(&normalize(input))?,
+        operators,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
 
     const TEST_INPUT: &str = "\
 190: 10 19
@@ -94,13 +308,294 @@ mod tests {
 292: 11 6 16 20
 ";
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("190: 10 19\n3267 81 40 27\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 14));
+
+        let error = parse_input("190: 10 1x9\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+
+        let error = parse_input("190:\n").unwrap_err();
+        assert_eq!(error.expected, "at least one number");
+    }
+
+    fn evaluate<O: Operator>(numbers: &[u64], operators: &[&O]) -> Option<u64> {
+        operators
+            .iter()
+            .zip(&numbers[1..])
+            .try_fold(numbers[0], |acc, (op, &n)| op.apply(acc, n))
+    }
+
+    // Tries every combination of operators left to right.
+    fn brute_force<O: Operator>(expected: u64, numbers: &[u64], operators: &[O]) -> bool {
+        (0..numbers.len() - 1)
+            .map(|_| operators)
+            .multi_cartesian_product()
+            .any(|ops| evaluate(numbers, &ops) == Some(expected))
+    }
+
+    const ALL_OPERATORS: &[BuiltinOperator] = &[
+        BuiltinOperator::Add,
+        BuiltinOperator::Mul,
+        BuiltinOperator::Concat(10),
+        BuiltinOperator::Concat(2),
+        BuiltinOperator::Sub,
+        BuiltinOperator::Div,
+        BuiltinOperator::Pow,
+        BuiltinOperator::Xor,
+    ];
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(BuiltinOperator::Concat(10).apply(12, 345), Some(12345));
+        assert_eq!(BuiltinOperator::Concat(10).apply(12, 0), Some(120));
+        assert_eq!(BuiltinOperator::Concat(2).apply(0b101, 0b11), Some(0b10111));
+        assert_eq!(BuiltinOperator::Sub.apply(3, 5), None);
+        assert_eq!(BuiltinOperator::Div.apply(7, 2), Some(3));
+        assert_eq!(BuiltinOperator::Div.apply(7, 0), None);
+        assert_eq!(BuiltinOperator::Pow.apply(3, 4), Some(81));
+        assert_eq!(BuiltinOperator::Pow.apply(2, 64), None);
+        assert_eq!(BuiltinOperator::Pow.apply(1, u64::MAX), Some(1));
+        assert_eq!(BuiltinOperator::Xor.apply(0b1100, 0b1010), Some(0b0110));
+        assert_eq!(BuiltinOperator::Mul.apply(u64::MAX, 2), None);
+    }
+
+    #[test]
+    fn test_concat_invalid_base() {
+        for base in [0, 1] {
+            assert_eq!(BuiltinOperator::Concat(base).apply(5, 7), None);
+            assert_eq!(
+                BuiltinOperator::Concat(base).inverse(57, 7),
+                Inverse::Impossible
+            );
+            assert_eq!(
+                BuiltinOperator::Concat(base).inverse(5, 7),
+                Inverse::Impossible
+            );
+        }
+        assert!(!find_combination(
+            57,
+            &[5, 7],
+            &[BuiltinOperator::Concat(0)]
+        ));
+    }
+
+    #[test]
+    fn test_inverse_matches_apply() {
+        for &op in ALL_OPERATORS {
+            for n in 0..12 {
+                for result in 0..300 {
+                    let accs = (0..1024)
+                        .filter(|&acc| op.apply(acc, n) == Some(result))
+                        .collect::<Vec<_>>();
+                    match op.inverse(result, n) {
+                        Inverse::Impossible => assert_eq!(accs, [], "{} {} = {}", op, n, result),
+                        Inverse::Unique(acc) => {
+                            assert_eq!(accs, [acc], "{} {} = {}", op, n, result)
+                        }
+                        Inverse::Ambiguous => {}
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            BuiltinOperator::Pow.inverse(u64::MAX, 2),
+            Inverse::Impossible
+        );
+        assert_eq!(
+            BuiltinOperator::Pow.inverse(4294967295u64.pow(2), 2),
+            Inverse::Unique(4294967295)
+        );
+        assert_eq!(
+            BuiltinOperator::Sub.inverse(u64::MAX, 1),
+            Inverse::Impossible
+        );
+
+        // Past 2^53, floating point estimates can be far from the root.
+        let large = [
+            (1 << 62) + 300,
+            u64::MAX - 5,
+            u64::MAX,
+            1 << 63,
+            3u64.pow(40),
+            4294967295,
+            4294967296,
+            2642245,
+            2642246,
+        ];
+        for &op in ALL_OPERATORS {
+            for acc in large {
+                for n in [0, 1, 2, 3, 7, 10, 40, 1 << 40] {
+                    if let Some(result) = op.apply(acc, n) {
+                        let inverse = op.inverse(result, n);
+                        assert!(
+                            inverse == Inverse::Unique(acc) || inverse == Inverse::Ambiguous,
+                            "{} {} {}: {:?}",
+                            acc,
+                            op,
+                            n,
+                            inverse
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            BuiltinOperator::Pow.inverse((1 << 62) + 300, 1),
+            Inverse::Unique((1 << 62) + 300)
+        );
+        assert_eq!(
+            BuiltinOperator::Pow.inverse(u64::MAX - 5, 1),
+            Inverse::Unique(u64::MAX - 5)
+        );
+        assert_eq!(
+            BuiltinOperator::Pow.inverse(2642245u64.pow(3), 3),
+            Inverse::Unique(2642245)
+        );
+        assert_eq!(
+            BuiltinOperator::Pow.inverse(2642245u64.pow(3) + 1, 3),
+            Inverse::Impossible
+        );
+        assert_eq!(
+            BuiltinOperator::Pow.inverse(1 << 63, 63),
+            Inverse::Unique(2)
+        );
+        assert_eq!(
+            BuiltinOperator::Pow.inverse(u64::MAX, 64),
+            Inverse::Impossible
+        );
+    }
+
+    #[test]
+    fn test_operator_from_str() {
+        assert_eq!("add".parse(), Ok(BuiltinOperator::Add));
+        assert_eq!("*".parse(), Ok(BuiltinOperator::Mul));
+        assert_eq!("concat".parse(), Ok(BuiltinOperator::Concat(10)));
+        assert_eq!("||".parse(), Ok(BuiltinOperator::Concat(10)));
+        assert_eq!("concat3".parse(), Ok(BuiltinOperator::Concat(3)));
+        assert!("concat1".parse::<BuiltinOperator>().is_err());
+        assert!("concatx".parse::<BuiltinOperator>().is_err());
+        assert!("mod".parse::<BuiltinOperator>().is_err());
+        for &op in ALL_OPERATORS {
+            assert_eq!(op.to_string().parse(), Ok(op));
+        }
+    }
+
+    #[test]
+    fn test_find_combination_matches_brute_force() {
+        let equations = parse_input(TEST_INPUT).unwrap();
+        let extra = [
+            (0, vec![5, 0]),
+            (0, vec![0, 0, 3]),
+            (10, vec![1, 0]),
+            (100, vec![10, 0]),
+            (1020, vec![10, 2, 0]),
+            (50, vec![5, 0, 0]),
+            (1, vec![5, 3, 0]),
+            (4, vec![17, 4, 1]),
+            (2, vec![9, 3, 6, 2]),
+            (7, vec![7]),
+            (8, vec![7]),
+        ];
+        let what_ifs: &[&[BuiltinOperator]] = &[
+            &[BuiltinOperator::Sub, BuiltinOperator::Add],
+            &[BuiltinOperator::Div, BuiltinOperator::Mul],
+            &[BuiltinOperator::Pow, BuiltinOperator::Xor],
+            &[BuiltinOperator::Concat(2), BuiltinOperator::Sub],
+            ALL_OPERATORS,
+        ];
+        for (expected, numbers) in equations.iter().cloned().chain(extra) {
+            for operators in [PART1_OPERATORS, PART2_OPERATORS].iter().chain(what_ifs) {
+                assert_eq!(
+                    find_combination(expected, &numbers, operators),
+                    brute_force(expected, &numbers, operators),
+                    "{}: {:?} with {:?}",
+                    expected,
+                    numbers,
+                    operators
+                );
+            }
+        }
+    }
+
+    // 24 operators give 3^24 (about 2.8 * 10^11) combinations, far too many
+    // to try one by one.
+    // An operator of one's own, which cannot be undone uniquely.
+    struct Max;
+
+    impl Operator for Max {
+        fn apply(&self, acc: u64, n: u64) -> Option<u64> {
+            Some(acc.max(n))
+        }
+    }
+
+    #[test]
+    fn test_custom_operator() {
+        let operators: &[&dyn Operator] = &[&BuiltinOperator::Add, &Max, &BuiltinOperator::Mul];
+        for (expected, numbers) in parse_input(TEST_INPUT).unwrap() {
+            for numbers in [numbers.clone(), [numbers, vec![3, 200]].concat()] {
+                assert_eq!(
+                    find_combination(expected, &numbers, operators),
+                    brute_force(expected, &numbers, operators),
+                    "{}: {:?}",
+                    expected,
+                    numbers
+                );
+            }
+        }
+        assert!(find_combination(19, &[10, 19, 4], operators));
+        assert!(!find_combination(19, &[10, 19, 4], PART1_OPERATORS));
+        assert_eq!(
+            calibrate(
+                "15: 15 2 1
+",
+                &[&Max as &dyn Operator]
+            )
+            .unwrap(),
+            15
+        );
+    }
+
+    #[test]
+    fn test_many_operands() {
+        let numbers = [
+            7, 3, 12, 5, 8, 1, 9, 2, 6, 4, 11, 3, 7, 2, 5, 9, 1, 8, 3, 6, 2, 4, 7, 5, 3,
+        ];
+        let expected = numbers.iter().sum::<u64>();
+        assert!(find_combination(expected, &numbers, PART1_OPERATORS));
+
+        let operators = "++*+|++*+++|+*+++*++|+++"
+            .chars()
+            .map(|c| match c {
+                '+' => BuiltinOperator::Add,
+                '*' => BuiltinOperator::Mul,
+                _ => BuiltinOperator::Concat(10),
+            })
+            .collect::<Vec<_>>();
+        let expected = evaluate(&numbers, &operators.iter().collect::<Vec<_>>()).unwrap();
+        assert!(find_combination(expected, &numbers, PART2_OPERATORS));
+        assert!(!find_combination(expected, &numbers, PART1_OPERATORS));
+        assert!(!find_combination(u64::MAX, &numbers, PART2_OPERATORS));
+    }
+
+    #[test]
+    fn test_calibrate() {
+        assert_eq!(calibrate(TEST_INPUT, PART2_OPERATORS).unwrap(), 11387);
+        // 190 = 10 * 19 and 3267 = 81 * 40 ^ 27.
+        assert_eq!(
+            calibrate(TEST_INPUT, &[BuiltinOperator::Mul, BuiltinOperator::Xor]).unwrap(),
+            190 + 3267
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(TEST_INPUT), 3749);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 3749);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(TEST_INPUT), 11387);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT).unwrap()), 11387);
     }
 }
@@ -1,98 +1,88 @@
-use anyhow::Result;
 use itertools::Itertools;
-use std::fs;
 
-use crate::types::{Point, Vector};
+use super::Solution;
+use crate::parse::{self, ParseError};
+use crate::types::{Grid, Point};
+use anyhow::Result;
+
+pub struct Day08;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day08.txt")?;
+impl Solution for Day08 {
+    const DAY: u8 = 8;
 
-    // Part 1
-    let result1 = solve_part1(&input);
-    println!("Part 1: {}", result1);
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    // Part 2
-    let result2 = solve_part2(&input);
-    println!("Part 2: {}", result2);
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
 
-    Ok(())
+    fn part1(input: &Self::Input) -> usize {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        solve_part2(input)
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    parse::grid(
+        Day08::DAY,
+        input,
+        |c| (c == '.' || c.is_ascii_alphanumeric()).then_some(c),
+        "`.` or an antenna frequency",
+    )
 }
 
-fn find_antennas(grid: &[Vec<char>]) -> Vec<(char, Point)> {
+fn find_antennas(grid: &Grid<char>) -> Vec<(char, Point)> {
     grid.iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter().enumerate().map(move |(x, &c)| {
-                (
-                    c,
-                    Point {
-                        x: x as isize,
-                        y: y as isize,
-                    },
-                )
-            })
-        })
-        .filter(|&(c, _)| c != '.')
+        .filter(|&(_, &c)| c != '.')
+        .map(|(p, &c)| (c, p))
         .collect()
 }
 
-fn find_antinode(p1: &Point, p2: &Point) -> Point {
-    let diff = Vector {
-        dx: (p1.x - p2.x),
-        dy: (p1.y - p2.y),
-    };
-    p1 + diff
-}
-
-fn find_all_antinodes(grid: &[Vec<char>], p1: &Point, p2: &Point) -> Vec<Point> {
-    let diff = Vector {
-        dx: (p1.x - p2.x),
-        dy: (p1.y - p2.y),
-    };
-    let pos = (1..)
-        .map(|i| p1 + &diff * i)
-        .take_while(|p| within_bounds(p, grid));
-    let neg = (1..)
-        .map(|i| p1 - &diff * i)
-        .take_while(|p| within_bounds(p, grid));
-    pos.chain(neg).collect()
+fn find_antinode(p1: Point, p2: Point) -> Point {
+    p1 + (p1 - p2)
 }
 
-fn within_bounds(p: &Point, grid: &[Vec<char>]) -> bool {
-    p.x >= 0 && p.x < grid[0].len() as isize && p.y >= 0 && p.y < grid.len() as isize
+// Every grid point on the line through both antennas, stepping by the
+// smallest integer vector along it so no point in between is skipped.
+fn find_all_antinodes(grid: &Grid<char>, p1: Point, p2: Point) -> Vec<Point> {
+    let step = (p1 - p2).primitive();
+    p1.ray(step)
+        .within(grid)
+        .chain(p1.ray(-step).within(grid))
+        .collect()
 }
 
-fn solve_part1(input: &str) -> usize {
-    let grid = parse_input(input);
-    let antennas = find_antennas(&grid);
+fn solve_part1(grid: &Grid<char>) -> usize {
+    let bounds = grid.bounds();
+    let antennas = find_antennas(grid);
     antennas
         .iter()
-        .flat_map(|(c, p1)| {
+        .flat_map(|&(c, p1)| {
             antennas
                 .iter()
-                .filter(|(c2, p2)| c2 == c && p2 != p1)
-                .map(|(_, p2)| find_antinode(p1, p2))
+                .filter(|&&(c2, p2)| c2 == c && p2 != p1)
+                .map(|&(_, p2)| find_antinode(p1, p2))
                 .collect::<Vec<_>>()
         })
-        .filter(|p| within_bounds(p, &grid))
+        .filter(|p| bounds.contains(p))
         .unique()
         .count()
 }
 
-fn solve_part2(input: &str) -> usize {
-    let grid = parse_input(input);
-    let antennas = find_antennas(&grid);
+fn solve_part2(grid: &Grid<char>) -> usize {
+    let antennas = find_antennas(grid);
     antennas
         .iter()
-        .flat_map(|(c, p1)| {
+        .flat_map(|&(c, p1)| {
             antennas
                 .iter()
-                .filter(|(c2, p2)| c2 == c && p2 != p1)
-                .flat_map(|(_, p2)| find_all_antinodes(&grid, p1, p2))
+                .filter(|&&(c2, p2)| c2 == c && p2 != p1)
+                .flat_map(|&(_, p2)| find_all_antinodes(grid, p1, p2))
                 .collect::<Vec<_>>()
         })
         .unique()
@@ -147,28 +137,34 @@ T.........
     #[test]
     fn test_find_antinode() {
         assert_eq!(
-            find_antinode(&Point { x: 4, y: 3 }, &Point { x: 5, y: 5 }),
+            find_antinode(Point { x: 4, y: 3 }, Point { x: 5, y: 5 }),
             Point { x: 3, y: 1 }
         );
     }
 
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("..a\n.#.\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
     #[test]
     fn test_simple_input() {
-        assert_eq!(solve_part1(TEST_INPUT_2), 2);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT_2).unwrap()), 2);
     }
 
     #[test]
     fn test_simple_input_2() {
-        assert_eq!(solve_part2(TEST_INPUT_3), 9);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT_3).unwrap()), 9);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(TEST_INPUT), 14);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 14);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(TEST_INPUT), 34);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT).unwrap()), 34);
     }
 }
@@ -1,22 +1,29 @@
+use super::Solution;
+use crate::parse::{self, ParseError};
 use anyhow::Result;
 use itertools::Itertools;
-use std::{
-    fs,
-    iter::{once, repeat_n},
-};
+use std::iter::repeat_n;
 
-pub fn solve() -> Result<()> {
-    let input = fs::read_to_string("inputs/day09.txt")?;
+pub struct Day09;
 
-    // Part 1
-    let result1 = solve_part1(&input);
-    println!("Part 1: {}", result1);
+impl Solution for Day09 {
+    const DAY: u8 = 9;
 
-    // Part 2
-    let result2 = solve_part2(&input);
-    println!("Part 2: {}", result2);
+    type Input = Vec<FileBlob>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> usize {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> usize {
+        solve_part2(input)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,49 +33,44 @@ enum FileBlock {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct FileBlob {
+pub struct FileBlob {
     id: usize,
     size: usize,
     free_space: usize,
 }
 
-fn parse_input(input: &str) -> Vec<FileBlock> {
-    input
-        .chars()
-        .chain(once('0'))
-        .chunks(2)
-        .into_iter()
-        .enumerate()
-        .flat_map(|(id, chunk)| {
-            let (size, space_after) = chunk.collect_tuple().unwrap();
-            let size = size.to_digit(10).unwrap() as usize;
-            let space_after = space_after.to_digit(10).unwrap() as usize;
-            repeat_n(FileBlock::Id(id), size).chain(repeat_n(FileBlock::Free, space_after))
+fn parse_input(input: &str) -> Result<Vec<FileBlob>, ParseError> {
+    let mut lines = parse::lines(Day09::DAY, input);
+    let line = lines
+        .next()
+        .filter(|line| !line.text.is_empty())
+        .ok_or_else(|| ParseError::new(Day09::DAY, 1, "", 1, "a disk map"))?;
+    if let Some(extra) = lines.next() {
+        return Err(extra.error_at_offset(0, "end of input"));
+    }
+
+    let digits = line
+        .text
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|d| d as usize)
+                .ok_or_else(|| line.error_at_offset(i, "a digit"))
         })
-        .collect()
-}
+        .collect::<Result<Vec<_>, _>>()?;
 
-fn parse_input_to_blobs(input: &str) -> Vec<FileBlob> {
-    input
-        .chars()
-        .chain(once('0'))
+    Ok(digits
         .chunks(2)
-        .into_iter()
         .enumerate()
-        .map(|(id, chunk)| {
-            let (size, space_after) = chunk.collect_tuple().unwrap();
-            let size = size.to_digit(10).unwrap() as usize;
-            let space_after = space_after.to_digit(10).unwrap() as usize;
-            FileBlob {
-                id,
-                size,
-                free_space: space_after,
-            }
+        .map(|(id, chunk)| FileBlob {
+            id,
+            size: chunk[0],
+            free_space: chunk.get(1).copied().unwrap_or(0),
         })
-        .collect()
+        .collect())
 }
 
-fn sort_file_map_by_block(file_map: &mut Vec<FileBlock>) {
+fn sort_file_map_by_block(file_map: &mut [FileBlock]) {
     let mut i = 0;
     let mut last_i = file_map.len();
     while i < last_i {
@@ -86,7 +88,7 @@ fn sort_file_map_by_block(file_map: &mut Vec<FileBlock>) {
     }
 }
 
-fn blob_file_map_to_block_file_map(file_map: &Vec<FileBlob>) -> Vec<FileBlock> {
+fn blob_file_map_to_block_file_map(file_map: &[FileBlob]) -> Vec<FileBlock> {
     file_map
         .iter()
         .flat_map(|blob| {
@@ -135,7 +137,7 @@ fn sort_file_map_by_blob(file_map: &mut Vec<FileBlob>) {
     }
 }
 
-fn calculate_checksum(file_map: &Vec<FileBlock>) -> usize {
+fn calculate_checksum(file_map: &[FileBlock]) -> usize {
     file_map
         .iter()
         .enumerate()
@@ -146,14 +148,14 @@ fn calculate_checksum(file_map: &Vec<FileBlock>) -> usize {
         .sum()
 }
 
-fn solve_part1(input: &str) -> usize {
-    let mut file_map = parse_input(input);
+fn solve_part1(file_map: &[FileBlob]) -> usize {
+    let mut file_map = blob_file_map_to_block_file_map(file_map);
     sort_file_map_by_block(&mut file_map);
     calculate_checksum(&file_map)
 }
 
-fn solve_part2(input: &str) -> usize {
-    let mut file_map = parse_input_to_blobs(input);
+fn solve_part2(file_map: &[FileBlob]) -> usize {
+    let mut file_map = file_map.to_vec();
     sort_file_map_by_blob(&mut file_map);
     calculate_checksum(&blob_file_map_to_block_file_map(&file_map))
 }
@@ -162,7 +164,7 @@ fn solve_part2(input: &str) -> usize {
 mod tests {
     use super::*;
 
-    fn blob_file_map_to_string(file_map: &Vec<FileBlob>) -> String {
+    fn blob_file_map_to_string(file_map: &[FileBlob]) -> String {
         file_map
             .iter()
             .flat_map(|blob| {
@@ -182,7 +184,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let file_map: Vec<FileBlock> = parse_input("12345");
+        let file_map = blob_file_map_to_block_file_map(&parse_input("12345").unwrap());
         assert_eq!(
             file_map,
             vec![
@@ -205,19 +207,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("12a45").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+
+        let error = parse_input("12345\n6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        assert!(parse_input("").is_err());
+    }
+
+    #[test]
+    fn test_parse_even_length() {
+        assert_eq!(
+            blob_file_map_to_string(&parse_input("1234").unwrap()),
+            "0..111...."
+        );
+    }
+
     #[test]
     fn test_simple_part1() {
-        assert_eq!(solve_part1("12345"), 60);
+        assert_eq!(solve_part1(&parse_input("12345").unwrap()), 60);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(TEST_INPUT), 1928);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 1928);
     }
 
     #[test]
     fn test_part2_sort_2() {
-        let mut file_map = parse_input_to_blobs(TEST_INPUT_2);
+        let mut file_map = parse_input(TEST_INPUT_2).unwrap();
         println!("{}", blob_file_map_to_string(&file_map));
         sort_file_map_by_blob(&mut file_map);
         println!("{}", blob_file_map_to_string(&file_map));
@@ -229,7 +250,7 @@ mod tests {
 
     #[test]
     fn test_part2_sort_3() {
-        let mut file_map = parse_input_to_blobs(TEST_INPUT_3);
+        let mut file_map = parse_input(TEST_INPUT_3).unwrap();
         sort_file_map_by_blob(&mut file_map);
         assert_eq!(
             blob_file_map_to_string(&file_map),
@@ -239,7 +260,7 @@ mod tests {
 
     #[test]
     fn test_part2_sort_4() {
-        let mut file_map = parse_input_to_blobs(TEST_INPUT_4);
+        let mut file_map = parse_input(TEST_INPUT_4).unwrap();
         sort_file_map_by_blob(&mut file_map);
         assert_eq!(
             blob_file_map_to_string(&file_map),
@@ -251,7 +272,7 @@ mod tests {
 
     #[test]
     fn test_part2_sort_5() {
-        let mut file_map = parse_input_to_blobs(TEST_INPUT_5);
+        let mut file_map = parse_input(TEST_INPUT_5).unwrap();
         sort_file_map_by_blob(&mut file_map);
         assert_eq!(
             blob_file_map_to_string(&file_map),
@@ -261,6 +282,6 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        assert_eq!(solve_part2(TEST_INPUT), 2858);
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT).unwrap()), 2858);
     }
 }
@@ -1,97 +1,1375 @@
-use std::ops::{Add, Mul, Neg, Sub};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-pub struct Point {
-    pub x: isize,
-    pub y: isize,
+/// Integer types usable as `Point` and `Vector` coordinates.
+pub trait Coord:
+    Copy
+    + Ord
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `self + rhs`, or `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Vector {
-    pub dx: isize,
-    pub dy: isize,
+macro_rules! impl_coord {
+    ($($t:ty)*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// A position. Points order by row, then column, i.e. in reading order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point<T = isize> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A displacement between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Vector<T = isize> {
+    pub dx: T,
+    pub dy: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(dx: T, dy: T) -> Self {
+        Self { dx, dy }
+    }
+}
+
+impl<T: Coord> PartialOrd for Point<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Coord> Ord for Point<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}, {}>", self.dx, self.dy)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: FromStr> FromStr for Point<T> {
+    type Err = String;
+
+    /// Parses `x,y`, e.g. `3,-4`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("invalid point `{}` (expected `x,y`)", s);
+        let (x, y) = s.split_once(',').ok_or_else(error)?;
+        let coord = |c: &str| c.trim().parse().map_err(|_| error());
+        Ok(Self::new(coord(x)?, coord(y)?))
+    }
+}
+
+impl<T> From<(T, T)> for Vector<T> {
+    fn from((dx, dy): (T, T)) -> Self {
+        Self { dx, dy }
+    }
+}
+
+impl<T> From<Vector<T>> for (T, T) {
+    fn from(vector: Vector<T>) -> Self {
+        (vector.dx, vector.dy)
+    }
+}
+
+fn abs_diff<T: Coord>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Coord> Point<T> {
+    /// Taxicab distance: the number of orthogonal steps between the points.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Chessboard distance: the number of king moves between the points.
+    pub fn chebyshev(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+
+    /// The points `self`, `self + step`, `self + 2 * step`, ... until a
+    /// coordinate would overflow; see `Ray::within` to stop at the edge of a
+    /// grid.
+    pub fn ray(self, step: impl Into<Vector<T>>) -> Ray<T> {
+        Ray {
+            next: Some(self),
+            step: step.into(),
+        }
+    }
 }
 
 impl Point {
-    pub fn new((x, y): (usize, usize)) -> Self {
-        Self {
-            x: x as isize,
-            y: y as isize,
+    /// The four orthogonally adjacent points, clockwise from north.
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        Direction::iter().map(move |direction| self + direction.to_vector())
+    }
+
+    /// The eight adjacent points including diagonals, clockwise from north.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        Direction8::iter().map(move |direction| self + direction.to_vector())
+    }
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Vector {
+    /// The shortest vector with integer components pointing the same way,
+    /// i.e. both components divided by their gcd. The zero vector stays zero.
+    pub fn primitive(self) -> Vector {
+        match gcd(self.dx, self.dy) {
+            0 => self,
+            divisor => Vector::new(self.dx / divisor, self.dy / divisor),
         }
     }
 }
 
-impl Add<Vector> for Point {
-    type Output = Point;
+/// Iterator returned by `Point::ray`.
+#[derive(Debug, Clone)]
+pub struct Ray<T = isize> {
+    next: Option<Point<T>>,
+    step: Vector<T>,
+}
+
+impl<T: Coord> Iterator for Ray<T> {
+    type Item = Point<T>;
 
-    fn add(self, rhs: Vector) -> Self::Output {
-        Point {
-            x: self.x + rhs.dx,
-            y: self.y + rhs.dy,
+    fn next(&mut self) -> Option<Point<T>> {
+        let point = self.next?;
+        self.next = point
+            .x
+            .checked_add(self.step.dx)
+            .zip(point.y.checked_add(self.step.dy))
+            .map(|(x, y)| Point::new(x, y));
+        Some(point)
+    }
+}
+
+impl Ray {
+    /// Stops the ray at the first point outside `bounds`, e.g. a `&Grid`.
+    pub fn within(self, bounds: impl Into<Rect>) -> impl Iterator<Item = Point> {
+        let bounds = bounds.into();
+        self.take_while(move |point| bounds.contains(point))
+    }
+}
+
+// Implements `lhs op rhs` for every mix of owned and borrowed operands in
+// terms of the owned-owned impl, which is cheap since both sides are `Copy`.
+macro_rules! forward_ref_binop {
+    (impl $imp:ident, $method:ident for $lhs:ty, $rhs:ty) => {
+        impl<T: Coord> $imp<$rhs> for &$lhs {
+            type Output = <$lhs as $imp<$rhs>>::Output;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl<T: Coord> $imp<&$rhs> for $lhs {
+            type Output = <$lhs as $imp<$rhs>>::Output;
+
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                self.$method(*rhs)
+            }
+        }
+
+        impl<T: Coord> $imp<&$rhs> for &$lhs {
+            type Output = <$lhs as $imp<$rhs>>::Output;
+
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                (*self).$method(*rhs)
+            }
+        }
+    };
+}
+
+// Same as `forward_ref_binop`, for the compound assignment operators.
+macro_rules! forward_ref_op_assign {
+    (impl $imp:ident, $method:ident for $lhs:ty, $rhs:ty) => {
+        impl<T: Coord> $imp<&$rhs> for $lhs {
+            fn $method(&mut self, rhs: &$rhs) {
+                self.$method(*rhs)
+            }
         }
+    };
+}
+
+// The operators between a point type and its vector type, component by
+// component, for owned and borrowed operands alike.
+macro_rules! point_vector_ops {
+    ($point:ident { $($p:ident),+ }, $vector:ident { $($v:ident),+ }) => {
+        impl<T: Coord> Add<$vector<T>> for $point<T> {
+            type Output = $point<T>;
+
+            fn add(self, rhs: $vector<T>) -> Self::Output {
+                $point { $($p: self.$p + rhs.$v),+ }
+            }
+        }
+
+        impl<T: Coord> Sub<$vector<T>> for $point<T> {
+            type Output = $point<T>;
+
+            fn sub(self, rhs: $vector<T>) -> Self::Output {
+                $point { $($p: self.$p - rhs.$v),+ }
+            }
+        }
+
+        impl<T: Coord> Sub<$point<T>> for $point<T> {
+            type Output = $vector<T>;
+
+            fn sub(self, rhs: $point<T>) -> Self::Output {
+                $vector { $($v: self.$p - rhs.$p),+ }
+            }
+        }
+
+        impl<T: Coord> Add<$vector<T>> for $vector<T> {
+            type Output = $vector<T>;
+
+            fn add(self, rhs: $vector<T>) -> Self::Output {
+                $vector { $($v: self.$v + rhs.$v),+ }
+            }
+        }
+
+        impl<T: Coord> Sub<$vector<T>> for $vector<T> {
+            type Output = $vector<T>;
+
+            fn sub(self, rhs: $vector<T>) -> Self::Output {
+                $vector { $($v: self.$v - rhs.$v),+ }
+            }
+        }
+
+        impl<T: Coord> Mul<T> for $vector<T> {
+            type Output = $vector<T>;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                $vector { $($v: self.$v * rhs),+ }
+            }
+        }
+
+        impl<T: Coord> Mul<T> for &$vector<T> {
+            type Output = $vector<T>;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                *self * rhs
+            }
+        }
+
+        impl<T: Coord + Neg<Output = T>> Neg for $vector<T> {
+            type Output = $vector<T>;
+
+            fn neg(self) -> Self::Output {
+                $vector { $($v: -self.$v),+ }
+            }
+        }
+
+        impl<T: Coord + Neg<Output = T>> Neg for &$vector<T> {
+            type Output = $vector<T>;
+
+            fn neg(self) -> Self::Output {
+                -*self
+            }
+        }
+
+        impl<T: Coord> AddAssign<$vector<T>> for $point<T> {
+            fn add_assign(&mut self, rhs: $vector<T>) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coord> SubAssign<$vector<T>> for $point<T> {
+            fn sub_assign(&mut self, rhs: $vector<T>) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T: Coord> AddAssign<$vector<T>> for $vector<T> {
+            fn add_assign(&mut self, rhs: $vector<T>) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Coord> SubAssign<$vector<T>> for $vector<T> {
+            fn sub_assign(&mut self, rhs: $vector<T>) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T: Coord> MulAssign<T> for $vector<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                *self = *self * rhs;
+            }
+        }
+
+        forward_ref_binop!(impl Add, add for $point<T>, $vector<T>);
+        forward_ref_binop!(impl Sub, sub for $point<T>, $vector<T>);
+        forward_ref_binop!(impl Sub, sub for $point<T>, $point<T>);
+        forward_ref_binop!(impl Add, add for $vector<T>, $vector<T>);
+        forward_ref_binop!(impl Sub, sub for $vector<T>, $vector<T>);
+
+        forward_ref_op_assign!(impl AddAssign, add_assign for $point<T>, $vector<T>);
+        forward_ref_op_assign!(impl SubAssign, sub_assign for $point<T>, $vector<T>);
+        forward_ref_op_assign!(impl AddAssign, add_assign for $vector<T>, $vector<T>);
+        forward_ref_op_assign!(impl SubAssign, sub_assign for $vector<T>, $vector<T>);
+    };
+}
+
+point_vector_ops!(Point { x, y }, Vector { dx, dy });
+
+/// An axis-aligned rectangle of points, from `min` inclusive to `max`
+/// exclusive like `Cuboid`, so that a `width` by `height` grid is
+/// `(0, 0)..(width, height)`. A rectangle with no points is empty, whatever
+/// its corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    pub const fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// The rectangle from the origin spanning `width` columns and `height` rows.
+    pub fn of_size(width: usize, height: usize) -> Self {
+        Self::new(
+            Point::new(0, 0),
+            Point::new(width as isize, height as isize),
+        )
+    }
+
+    /// The smallest rectangle containing all `points`; empty if there are none.
+    pub fn bounding(points: impl IntoIterator<Item = Point>) -> Self {
+        points
+            .into_iter()
+            .map(|point| Self::new(point, point + Vector::new(1, 1)))
+            .fold(Self::default(), |bounds, rect| bounds.union(&rect))
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y).max(0) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.min.x..self.max.x).contains(&point.x) && (self.min.y..self.max.y).contains(&point.y)
+    }
+
+    /// The points in both rectangles; empty if they do not overlap.
+    pub fn intersect(&self, other: &Rect) -> Rect {
+        Rect::new(
+            Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        )
+    }
+
+    /// The smallest rectangle containing both; an empty side is ignored.
+    pub fn union(&self, other: &Rect) -> Rect {
+        match (self.is_empty(), other.is_empty()) {
+            (_, true) => *self,
+            (true, false) => *other,
+            (false, false) => Rect::new(
+                Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+                Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+            ),
+        }
+    }
+
+    /// All points of the rectangle, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Rect { min, max } = *self;
+        (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| Point::new(x, y)))
     }
 }
 
-impl Add<Vector> for &Point {
-    type Output = Point;
+impl<T> From<&Grid<T>> for Rect {
+    fn from(grid: &Grid<T>) -> Self {
+        grid.bounds()
+    }
+}
 
-    fn add(self, rhs: Vector) -> Self::Output {
-        Point {
-            x: self.x + rhs.dx,
-            y: self.y + rhs.dy,
+/// A position in space. Points order by layer (z), then in reading order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point3<T = isize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// A displacement between two points in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Vector3<T = isize> {
+    pub dx: T,
+    pub dy: T,
+    pub dz: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Vector3<T> {
+    pub const fn new(dx: T, dy: T, dz: T) -> Self {
+        Self { dx, dy, dz }
+    }
+}
+
+impl<T: Coord> PartialOrd for Point3<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Coord> Ord for Point3<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.z, self.y, self.x).cmp(&(other.z, other.y, other.x))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}, {}, {}>", self.dx, self.dy, self.dz)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vector3<T> {
+    fn from((dx, dy, dz): (T, T, T)) -> Self {
+        Self { dx, dy, dz }
+    }
+}
+
+impl<T> From<Vector3<T>> for (T, T, T) {
+    fn from(vector: Vector3<T>) -> Self {
+        (vector.dx, vector.dy, vector.dz)
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    /// Taxicab distance: the number of axis-aligned steps between the points.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+}
+
+impl Point3 {
+    /// The six points sharing a face with this one.
+    pub fn neighbors6(self) -> impl Iterator<Item = Point3> {
+        [
+            Vector3::new(1, 0, 0),
+            Vector3::new(-1, 0, 0),
+            Vector3::new(0, 1, 0),
+            Vector3::new(0, -1, 0),
+            Vector3::new(0, 0, 1),
+            Vector3::new(0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |step| self + step)
+    }
+
+    /// The 26 points sharing a face, edge or corner with this one.
+    pub fn neighbors26(self) -> impl Iterator<Item = Point3> {
+        (-1..=1)
+            .flat_map(|dz| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dx| (dx, dy, dz))))
+            .filter(|&step| step != (0, 0, 0))
+            .map(move |step| self + Vector3::from(step))
+    }
+}
+
+point_vector_ops!(Point3 { x, y, z }, Vector3 { dx, dy, dz });
+
+/// An axis-aligned box in space, from `min` inclusive to `max` exclusive like
+/// `Rect`. A box with no points is empty, whatever its corners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T = isize> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Coord> Cuboid<T> {
+    pub const fn new(min: Point3<T>, max: Point3<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// The smallest box containing all `points`, or `None` if there are none.
+    pub fn bounding(points: impl IntoIterator<Item = Point3<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )
+        });
+        Some(Self::new(min, max + Vector3::new(T::ONE, T::ONE, T::ONE)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x >= self.max.x || self.min.y >= self.max.y || self.min.z >= self.max.z
+    }
+
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::ZERO;
         }
+        let size = self.max - self.min;
+        size.dx * size.dy * size.dz
+    }
+
+    pub fn contains(&self, point: &Point3<T>) -> bool {
+        (self.min.x..self.max.x).contains(&point.x)
+            && (self.min.y..self.max.y).contains(&point.y)
+            && (self.min.z..self.max.z).contains(&point.z)
+    }
+}
+
+impl Cuboid {
+    /// All points of the box, layer by layer and row by row within a layer.
+    pub fn points(&self) -> impl Iterator<Item = Point3> {
+        let Cuboid { min, max } = *self;
+        (min.z..max.z).flat_map(move |z| {
+            (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| Point3::new(x, y, z)))
+        })
     }
 }
 
-impl Add<&Vector> for &Point {
-    type Output = Point;
+/// One of the four axis-aligned directions, with y growing downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn to_vector(self) -> Vector {
+        match self {
+            Direction::North => Vector { dx: 0, dy: -1 },
+            Direction::East => Vector { dx: 1, dy: 0 },
+            Direction::South => Vector { dx: 0, dy: 1 },
+            Direction::West => Vector { dx: -1, dy: 0 },
+        }
+    }
+
+    /// Parses one of `^>v<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Parses one of `NESW`.
+    pub fn from_compass(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Direction::North),
+            'E' => Some(Direction::East),
+            'S' => Some(Direction::South),
+            'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
 
-    fn add(self, rhs: &Vector) -> Self::Output {
-        Point {
-            x: self.x + rhs.dx,
-            y: self.y + rhs.dy,
+    /// The lowercase name, e.g. `north`, as written to exported files.
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::North => "north",
+            Direction::East => "east",
+            Direction::South => "south",
+            Direction::West => "west",
         }
     }
 }
 
-impl Sub<Vector> for &Point {
-    type Output = Point;
+impl TryFrom<char> for Direction {
+    type Error = char;
 
-    fn sub(self, rhs: Vector) -> Self::Output {
-        let neg = -rhs;
-        self + neg
+    /// Accepts both arrows and compass letters.
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_arrow(c)
+            .or_else(|| Self::from_compass(c))
+            .ok_or(c)
     }
 }
 
-impl Mul<usize> for &Vector {
-    type Output = Vector;
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.to_vector()
+    }
+}
+
+/// One of the eight directions including diagonals, with y growing downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction8> {
+        Self::ALL.into_iter()
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
 
-    fn mul(self, rhs: usize) -> Self::Output {
-        Vector {
-            dx: self.dx * rhs as isize,
-            dy: self.dy * rhs as isize,
+    /// Turns 45 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub fn to_vector(self) -> Vector {
+        let (dx, dy) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+        Vector { dx, dy }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Self {
+        direction.to_vector()
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = String;
+
+    /// Parses a compass abbreviation such as `N` or `SW`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "N" => Ok(Direction8::North),
+            "NE" => Ok(Direction8::NorthEast),
+            "E" => Ok(Direction8::East),
+            "SE" => Ok(Direction8::SouthEast),
+            "S" => Ok(Direction8::South),
+            "SW" => Ok(Direction8::SouthWest),
+            "W" => Ok(Direction8::West),
+            "NW" => Ok(Direction8::NorthWest),
+            _ => Err(format!("invalid direction `{}`", s)),
         }
     }
 }
 
-impl Neg for Vector {
-    type Output = Vector;
+/// A rectangular grid stored row by row in a flat buffer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Why a string could not be parsed into a `Grid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridErrorKind {
+    InvalidChar,
+    RaggedRow { width: usize },
+    Empty,
+}
+
+/// Error from `Grid::from_str`, with a 1-based line and character column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: GridErrorKind,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid size mismatch");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
 
-    fn neg(self) -> Self::Output {
-        Vector {
-            dx: -self.dx,
-            dy: -self.dy,
+    /// Parses one row per line, mapping each character with `tile`. Fails on
+    /// characters `tile` rejects, rows of differing widths and empty input.
+    pub fn from_str(input: &str, tile: impl Fn(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (y, line) in input.lines().enumerate() {
+            let error = |column, kind| GridError {
+                line: y + 1,
+                column,
+                kind,
+            };
+            let start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                if width.is_some_and(|width| x >= width) {
+                    return Err(error(
+                        x + 1,
+                        GridErrorKind::RaggedRow {
+                            width: width.unwrap(),
+                        },
+                    ));
+                }
+                cells.push(tile(c).ok_or(error(x + 1, GridErrorKind::InvalidChar))?);
+            }
+            let row = cells.len() - start;
+            match width {
+                None if row == 0 => return Err(error(1, GridErrorKind::Empty)),
+                None => width = Some(row),
+                Some(width) if row != width => {
+                    return Err(error(row + 1, GridErrorKind::RaggedRow { width }))
+                }
+                Some(_) => {}
+            }
+            height += 1;
         }
+        match width {
+            Some(width) => Ok(Self::new(width, height, cells)),
+            None => Err(GridError {
+                line: 1,
+                column: 1,
+                kind: GridErrorKind::Empty,
+            }),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The rectangle covered by the grid.
+    pub fn bounds(&self) -> Rect {
+        Rect::of_size(self.width, self.height)
+    }
+
+    pub fn in_bounds(&self, point: &Point) -> bool {
+        self.bounds().contains(point)
+    }
+
+    fn index_of(&self, point: &Point) -> Option<usize> {
+        self.in_bounds(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        self.bounds().points()
+    }
+
+    /// All cells with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The rows from top to bottom, none if the grid has no cells.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x` from top to bottom. Panics if `x` is out of
+    /// bounds, unless the grid has no cells and so no columns to speak of.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width || self.cells.is_empty(),
+            "column {} out of bounds",
+            x
+        );
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// The cells from `start` stepping by `step` until leaving the grid.
+    pub fn walk(&self, start: &Point, step: &Vector) -> impl Iterator<Item = (Point, &T)> {
+        start
+            .ray(*step)
+            .map_while(|point| self.get(&point).map(|cell| (point, cell)))
+    }
+
+    /// The diagonal running down and to the right from `start`.
+    pub fn diagonal(&self, start: &Point) -> impl Iterator<Item = (Point, &T)> {
+        self.walk(start, &Vector { dx: 1, dy: 1 })
+    }
+
+    /// The diagonal running down and to the left from `start`.
+    pub fn anti_diagonal(&self, start: &Point) -> impl Iterator<Item = (Point, &T)> {
+        self.walk(start, &Vector { dx: -1, dy: 1 })
+    }
+
+    /// The first point, row by row, whose cell satisfies `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(point, _)| point)
+    }
+}
+
+impl<T> Index<&Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: &Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", point))
+    }
+}
+
+impl<T> IndexMut<&Point> for Grid<T> {
+    fn index_mut(&mut self, point: &Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{:?} out of bounds", point))
     }
 }
 
-impl Mul<isize> for Vector {
-    type Output = Vector;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::from_str("abc\ndef\n", Some).unwrap()
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_point_vector_arithmetic() {
+        let p = Point::new(3, 4);
+        let v = Vector::new(1, -2);
+        assert_eq!(p + v, Point::new(4, 2));
+        assert_eq!(&p + v, p + &v);
+        assert_eq!(&p - &v, Point::new(2, 6));
+        assert_eq!(Point::new(4, 2) - p, v);
+        assert_eq!(&Point::new(4, 2) - &p, v);
+        assert_eq!(v + v, v * 2);
+        assert_eq!(&v - &v, Vector::default());
+        assert_eq!(-v, Vector::new(-1, 2));
+        assert_eq!(-&v, -v);
+        assert_eq!(&v * 3, Vector::new(3, -6));
+
+        let mut q = p;
+        q += v;
+        q -= &v;
+        q += &v;
+        assert_eq!(q, p + v);
+
+        let mut w = v;
+        w *= 3;
+        w -= v;
+        w += &v;
+        assert_eq!(w, v * 3);
+    }
+
+    #[test]
+    fn test_point_generic_coordinates() {
+        let p: Point<u8> = Point::new(1, 2);
+        assert_eq!(p + Vector::new(3, 4), Point::new(4, 6));
+        let q: Point<i64> = (10, 20).into();
+        assert_eq!(<(i64, i64)>::from(q - Vector::new(1, 1)), (9, 19));
+    }
+
+    #[test]
+    fn test_point_order_and_display() {
+        let mut points = vec![Point::new(2, 0), Point::new(0, 1), Point::new(1, 0)];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![Point::new(1, 0), Point::new(2, 0), Point::new(0, 1)]
+        );
+        assert_eq!(Point::new(1, -2).to_string(), "(1, -2)");
+        assert_eq!(Vector::new(0, 1).to_string(), "<0, 1>");
+    }
+
+    #[test]
+    fn test_point_from_str() {
+        assert_eq!("3,-4".parse(), Ok(Point::new(3, -4)));
+        assert_eq!(" 1, 2".parse(), Ok(Point::new(1, 2)));
+        assert!("1;2".parse::<Point>().is_err());
+        assert!("-1,2".parse::<Point<usize>>().is_err());
+    }
+
+    #[test]
+    fn test_point_distances() {
+        let p = Point::new(1, 5);
+        let q = Point::new(4, 1);
+        assert_eq!(p.manhattan(q), 7);
+        assert_eq!(q.manhattan(p), 7);
+        assert_eq!(p.chebyshev(q), 4);
+        assert_eq!(Point::<u32>::new(3, 0).manhattan(Point::new(1, 2)), 4);
+    }
+
+    #[test]
+    fn test_point_neighbors() {
+        let p = Point::new(1, 1);
+        assert_eq!(
+            p.neighbors4().collect::<Vec<_>>(),
+            vec![
+                Point::new(1, 0),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(0, 1)
+            ]
+        );
+        assert_eq!(p.neighbors8().count(), 8);
+        assert!(p.neighbors8().all(|q| p.chebyshev(q) == 1));
+    }
+
+    #[test]
+    fn test_vector_primitive() {
+        assert_eq!(Vector::new(4, -6).primitive(), Vector::new(2, -3));
+        assert_eq!(Vector::new(-3, 0).primitive(), Vector::new(-1, 0));
+        assert_eq!(Vector::new(1, 1).primitive(), Vector::new(1, 1));
+        assert_eq!(Vector::new(0, 0).primitive(), Vector::new(0, 0));
+    }
+
+    #[test]
+    fn test_ray() {
+        let p = Point::new(0, 1);
+        assert_eq!(
+            p.ray(Direction8::NorthEast).take(3).collect::<Vec<_>>(),
+            vec![Point::new(0, 1), Point::new(1, 0), Point::new(2, -1)]
+        );
+        assert_eq!(
+            p.ray(Vector::new(1, 0)).within(&grid()).collect::<Vec<_>>(),
+            vec![Point::new(0, 1), Point::new(1, 1), Point::new(2, 1)]
+        );
+        assert_eq!(
+            Point::new(5, 5)
+                .ray(Direction::North)
+                .within(&grid())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_ray_stops_before_overflow() {
+        assert_eq!(
+            Point::<u8>::new(250, 0).ray((2, 1)).collect::<Vec<_>>(),
+            vec![Point::new(250, 0), Point::new(252, 1), Point::new(254, 2)]
+        );
+        assert_eq!(Point::new(0, isize::MAX).ray((0, 1)).count(), 1);
+    }
+
+    #[test]
+    fn test_rect() {
+        let rect = Rect::new(Point::new(1, 1), Point::new(4, 3));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (3, 2, 6));
+        assert!(rect.contains(&Point::new(1, 2)));
+        assert!(!rect.contains(&Point::new(4, 2)));
+        assert_eq!(rect.points().next(), Some(Point::new(1, 1)));
+        assert_eq!(rect.points().last(), Some(Point::new(3, 2)));
+        assert_eq!(rect.points().count(), rect.area());
+
+        let other = Rect::new(Point::new(3, 0), Point::new(6, 2));
+        assert_eq!(
+            rect.intersect(&other),
+            Rect::new(Point::new(3, 1), Point::new(4, 2))
+        );
+        assert_eq!(
+            rect.union(&other),
+            Rect::new(Point::new(1, 0), Point::new(6, 3))
+        );
+
+        let far = Rect::new(Point::new(10, 10), Point::new(11, 11));
+        assert!(rect.intersect(&far).is_empty());
+        assert_eq!(rect.intersect(&far).points().count(), 0);
+        assert_eq!(Rect::default().union(&far), far);
+    }
+
+    #[test]
+    fn test_rect_construction() {
+        assert_eq!(
+            Rect::bounding([Point::new(2, -1), Point::new(0, 3), Point::new(1, 1)]),
+            Rect::new(Point::new(0, -1), Point::new(3, 4))
+        );
+        assert!(Rect::bounding([]).is_empty());
+        assert_eq!(Rect::from(&grid()), Rect::of_size(3, 2));
+        assert!(Rect::of_size(0, 5).is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_point3_arithmetic() {
+        let p = Point3::new(1, 2, 3);
+        let v = Vector3::new(1, -1, 2);
+        assert_eq!(p + v, Point3::new(2, 1, 5));
+        assert_eq!(&p - &v, Point3::new(0, 3, 1));
+        assert_eq!(Point3::new(2, 1, 5) - p, v);
+        assert_eq!(v * 2, v + v);
+        assert_eq!(-v, Vector3::new(-1, 1, -2));
+
+        let mut q = p;
+        q += v;
+        q -= &v;
+        assert_eq!(q, p);
+        assert_eq!(p.to_string(), "(1, 2, 3)");
+        assert!(Point3::new(5, 5, 0) < Point3::new(0, 0, 1));
+    }
+
+    #[test]
+    fn test_point3_neighbors_and_distance() {
+        let p = Point3::new(0, 0, 0);
+        assert_eq!(p.neighbors6().count(), 6);
+        assert!(p.neighbors6().all(|q| p.manhattan(q) == 1));
+        assert_eq!(p.neighbors26().count(), 26);
+        assert!(!p.neighbors26().any(|q| q == p));
+        assert_eq!(p.manhattan(Point3::new(1, -2, 3)), 6);
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let bounds = Cuboid::bounding([
+            Point3::new(1, 5, -1),
+            Point3::new(3, 0, 2),
+            Point3::new(2, 2, 2),
+        ])
+        .unwrap();
+        assert_eq!(bounds.min, Point3::new(1, 0, -1));
+        assert_eq!(bounds.max, Point3::new(4, 6, 3));
+        assert!(bounds.contains(&Point3::new(3, 5, 2)));
+        assert!(!bounds.contains(&Point3::new(2, 6, 0)));
+        assert_eq!(bounds.volume(), 3 * 6 * 4);
+        assert_eq!(bounds.points().count(), bounds.volume() as usize);
+        assert_eq!(bounds.points().next(), Some(bounds.min));
+        assert_eq!(Cuboid::<isize>::bounding([]), None);
 
-    fn mul(self, rhs: isize) -> Self::Output {
-        Vector {
-            dx: self.dx * rhs,
-            dy: self.dy * rhs,
+        let flat = Cuboid::new(Point3::new(0, 0, 0), Point3::new(2, 2, 0));
+        assert!(flat.is_empty());
+        assert_eq!((flat.volume(), flat.points().count()), (0, 0));
+    }
+
+    #[test]
+    fn test_direction_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        assert!(Direction::iter().all(|d| d.turn_right().turn_left() == d));
+        assert!(Direction::iter().all(|d| d.turn_right().turn_right() == d.opposite()));
+    }
+
+    #[test]
+    fn test_direction_parse() {
+        assert_eq!(Direction::from_arrow('v'), Some(Direction::South));
+        assert_eq!(Direction::from_arrow('S'), None);
+        assert_eq!(Direction::try_from('W'), Ok(Direction::West));
+        assert_eq!(Direction::try_from('<'), Ok(Direction::West));
+        assert_eq!(Direction::try_from('x'), Err('x'));
+        assert!(Direction::iter().all(|d| Direction::from_arrow(d.arrow()) == Some(d)));
+    }
+
+    #[test]
+    fn test_direction_names() {
+        assert_eq!(
+            Direction::ALL.map(Direction::name),
+            ["north", "east", "south", "west"]
+        );
+    }
+
+    #[test]
+    fn test_direction_vectors() {
+        assert_eq!(Direction::North.to_vector(), Vector { dx: 0, dy: -1 });
+        assert!(Direction::iter().all(|d| d.opposite().to_vector() == -d.to_vector()));
+        assert!(Direction::iter().all(|d| Direction8::from(d).to_vector() == d.to_vector()));
+    }
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!(Direction8::iter().count(), 8);
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthEast.opposite(), Direction8::SouthWest);
+        assert!(Direction8::SouthEast.is_diagonal());
+        assert_eq!(Direction8::SouthWest.to_vector(), Vector { dx: -1, dy: 1 });
+        assert_eq!("NE".parse(), Ok(Direction8::NorthEast));
+        assert!("Q".parse::<Direction8>().is_err());
+    }
+
+    #[test]
+    fn test_grid_from_str() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(&Point { x: 2, y: 1 }), Some(&'f'));
+        assert_eq!(grid.get(&Point { x: 3, y: 0 }), None);
+        assert_eq!(grid.get(&Point { x: 0, y: -1 }), None);
+    }
+
+    #[test]
+    fn test_grid_from_str_errors() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(
+            Grid::from_str("12\n3x\n", digit),
+            Err(GridError {
+                line: 2,
+                column: 2,
+                kind: GridErrorKind::InvalidChar
+            })
+        );
+        assert_eq!(
+            Grid::from_str("12\n345\n", digit).unwrap_err().kind,
+            GridErrorKind::RaggedRow { width: 2 }
+        );
+        assert_eq!(
+            Grid::from_str("12\n3\n", digit).unwrap_err(),
+            GridError {
+                line: 2,
+                column: 2,
+                kind: GridErrorKind::RaggedRow { width: 2 }
+            }
+        );
+        assert_eq!(
+            Grid::from_str("", digit).unwrap_err().kind,
+            GridErrorKind::Empty
+        );
+    }
+
+    #[test]
+    fn test_grid_get_mut() {
+        let mut grid = grid();
+        *grid.get_mut(&Point { x: 1, y: 0 }).unwrap() = 'x';
+        grid[&Point { x: 0, y: 1 }] = 'y';
+        assert_eq!(grid.row(0), &['a', 'x', 'c']);
+        assert_eq!(grid.row(1), &['y', 'e', 'f']);
+    }
+
+    #[test]
+    fn test_grid_iterators() {
+        let grid = grid();
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.diagonal(&Point { x: 0, y: 0 })
+                .map(|(_, c)| c)
+                .collect::<String>(),
+            "ae"
+        );
+        assert_eq!(
+            grid.anti_diagonal(&Point { x: 2, y: 0 })
+                .map(|(_, c)| c)
+                .collect::<String>(),
+            "ce"
+        );
+        assert_eq!(grid.points().nth(4), Some(Point { x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        for grid in [Grid::<char>::new(0, 3, vec![]), Grid::filled(0, 0, '.')] {
+            assert_eq!(grid.rows().count(), 0);
+            assert_eq!(grid.column(0).count(), 0);
+            assert_eq!(grid.points().count(), 0);
         }
+        let grid = Grid::filled(3, 0, '.');
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.column(2).count(), 0);
+    }
+
+    #[test]
+    fn test_grid_find() {
+        let grid = Grid::from_str("a.b\n.a.\n", Some).unwrap();
+        assert_eq!(grid.find(|&c| c == 'b'), Some(Point { x: 2, y: 0 }));
+        assert_eq!(grid.find(|&c| c == 'z'), None);
+        assert_eq!(
+            grid.positions_of(&'a').collect::<Vec<_>>(),
+            vec![Point { x: 0, y: 0 }, Point { x: 1, y: 1 }]
+        );
     }
 }
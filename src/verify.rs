@@ -0,0 +1,213 @@
+use crate::{answer::Answer, input::resolve_dir, solutions::Part};
+use anyhow::{anyhow, bail, Context, Result};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+/// Environment variable overriding the directory accepted answers are kept in.
+pub const ANSWERS_DIR_VAR: &str = "AOC_ANSWERS_DIR";
+
+/// Accepted answers for one day, as stored in `answers/dayNN.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recorded {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Recorded {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: Answer) {
+        match part {
+            Part::One => self.part1 = Some(answer),
+            Part::Two => self.part2 = Some(answer),
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        let table: Table = contents.parse()?;
+        if let Some(key) = table
+            .keys()
+            .find(|key| !["part1", "part2"].contains(&key.as_str()))
+        {
+            bail!("unexpected key `{}`", key);
+        }
+        Ok(Self {
+            part1: table.get("part1").map(to_answer).transpose()?,
+            part2: table.get("part2").map(to_answer).transpose()?,
+        })
+    }
+
+    fn to_toml(&self) -> String {
+        let mut table = Table::new();
+        for (key, answer) in [("part1", &self.part1), ("part2", &self.part2)] {
+            if let Some(answer) = answer {
+                table.insert(key.to_string(), to_value(answer));
+            }
+        }
+        table.to_string()
+    }
+}
+
+fn to_answer(value: &Value) -> Result<Answer> {
+    match value {
+        Value::Integer(n) => u64::try_from(*n)
+            .map(Answer::Int)
+            .map_err(|_| anyhow!("negative answer {}", n)),
+        Value::String(s) => Ok(Answer::Text(s.clone())),
+        _ => bail!("answers must be integers or strings, got {}", value),
+    }
+}
+
+fn to_value(answer: &Answer) -> Value {
+    match answer {
+        // TOML integers are signed, so answers that do not fit are stored as
+        // text and still compare equal through `Status::of`.
+        Answer::Int(n) => i64::try_from(*n)
+            .map(Value::Integer)
+            .unwrap_or_else(|_| Value::String(n.to_string())),
+        Answer::Text(s) => Value::String(s.clone()),
+    }
+}
+
+/// Outcome of comparing a computed answer against the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Mismatch { expected: Answer },
+    Unrecorded,
+}
+
+impl Status {
+    pub fn of(expected: Option<&Answer>, actual: &Answer) -> Self {
+        match expected {
+            None => Status::Unrecorded,
+            Some(expected) if expected.to_string() == actual.to_string() => Status::Ok,
+            Some(expected) => Status::Mismatch {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+/// Directory of `dayNN.toml` files with the accepted answers for each day.
+#[derive(Debug, Clone)]
+pub struct AnswerStore {
+    dir: PathBuf,
+}
+
+impl AnswerStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Picks the answers directory the same way as the inputs directory,
+    /// using `AOC_ANSWERS_DIR` and `answers`.
+    pub fn resolve(dir: Option<PathBuf>) -> Self {
+        Self::new(resolve_dir(dir, env::var_os(ANSWERS_DIR_VAR), "answers"))
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{:02}.toml", day))
+    }
+
+    /// Loads the recorded answers for `day`, which are empty if the day has
+    /// no answers file yet.
+    pub fn load(&self, day: u8) -> Result<Recorded> {
+        let path = self.path(day);
+        if !path.exists() {
+            return Ok(Recorded::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Recorded::parse(&contents)
+            .with_context(|| format!("invalid answers file {}", path.display()))
+    }
+
+    pub fn save(&self, day: u8, recorded: &Recorded) -> Result<()> {
+        let path = self.path(day);
+        fs::create_dir_all(&self.dir)?;
+        fs::write(&path, recorded.to_toml())
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let recorded = Recorded::parse("part1 = 42\npart2 = \"ABC\"\n").unwrap();
+        assert_eq!(recorded.part1, Some(Answer::Int(42)));
+        assert_eq!(recorded.part2, Some(Answer::Text("ABC".to_string())));
+
+        let recorded = Recorded::parse("part1 = 1\n").unwrap();
+        assert_eq!(recorded.get(Part::Two), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_values() {
+        assert!(Recorded::parse("part1 = -1\n").is_err());
+        assert!(Recorded::parse("part1 = 1.5\n").is_err());
+        assert!(Recorded::parse("part3 = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut recorded = Recorded::default();
+        recorded.set(Part::One, Answer::Int(u64::MAX));
+        recorded.set(Part::Two, Answer::Text("a \"b\"".to_string()));
+        let parsed = Recorded::parse(&recorded.to_toml()).unwrap();
+        assert_eq!(parsed.part2, recorded.part2);
+        assert_eq!(
+            Status::of(parsed.get(Part::One), &Answer::Int(u64::MAX)),
+            Status::Ok
+        );
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(Status::of(None, &Answer::Int(1)), Status::Unrecorded);
+        assert_eq!(
+            Status::of(Some(&Answer::Int(1)), &Answer::Int(1)),
+            Status::Ok
+        );
+        assert_eq!(
+            Status::of(Some(&Answer::Int(1)), &Answer::Int(2)),
+            Status::Mismatch {
+                expected: Answer::Int(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_missing_day() {
+        let store = AnswerStore::new("does-not-exist");
+        assert_eq!(store.load(1).unwrap(), Recorded::default());
+    }
+
+    #[test]
+    fn test_recorded_answers_are_valid() {
+        let store = AnswerStore::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("answers"));
+        // Days that were just scaffolded have no answers file yet.
+        for day in crate::solutions::all() {
+            if !store.path(day.number).exists() {
+                continue;
+            }
+            let recorded = store.load(day.number).unwrap();
+            assert!(recorded.part1.is_some() && recorded.part2.is_some());
+        }
+    }
+}